@@ -1,38 +1,102 @@
 use std::f32::consts::PI;
 
-use bevy::prelude::*;
-use noughts_and_crosses_core::messages::ClientMessage;
+use bevy::{prelude::*, render::render_resource::Face};
+use noughts_and_crosses_core::{
+    game::{Game, GameBoard},
+    game_state::Position,
+    gobblet::{GobbletMove, Size},
+    gravity::Column,
+    messages::ClientMessage,
+    qubic::QubicBoard,
+    Rules,
+};
 
-use crate::{client::ClientEvent, game_state::GameState};
+use crate::{
+    client::ClientEvent,
+    game_state::{GameState, GobbletPick},
+};
 
 pub struct Plugin;
 
-const TILE_SIZE: f32 = 5.0;
+pub const TILE_SIZE: f32 = 5.0;
 const TILE_GAP: f32 = 0.5;
+// the extra space between the small boards of ultimate and notakto games
+const SUB_BOARD_GAP: f32 = 1.5;
+// how high above the board pieces are dropped from in gravity games, and how long they take to land
+const DROP_HEIGHT: f32 = 6.0;
+const DROP_SECONDS: f32 = 0.4;
+// the space between the layers of a qubic cube, and how see-through its tiles are
+const LAYER_GAP: f32 = 5.0;
+const LAYER_ALPHA: f32 = 0.35;
+// how see-through the piece about to vanish in infinite games becomes, and how long it takes
+const VANISHING_ALPHA: f32 = 0.25;
+const FADE_SECONDS: f32 = 1.0;
+// how big small and medium gobblet pieces are, compared to large ones
+const SMALL_SCALE: f32 = 0.45;
+const MEDIUM_SCALE: f32 = 0.7;
 
 impl bevy::app::Plugin for Plugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, setup)
-            .add_systems(Update, update)
+            .add_systems(
+                Update,
+                (
+                    spawn_tiles,
+                    fade_layers,
+                    update,
+                    fall,
+                    fade_vanishing,
+                    scale_pieces,
+                )
+                    .chain(),
+            )
             .add_event::<HoverEvent>()
             .add_event::<ClickEvent>();
     }
 }
 
+/// the ray from the camera through a click. Layered boards are stacked, so a ray can pass over
+/// several tiles.
 #[derive(Event)]
-pub struct ClickEvent(pub Vec3);
+pub struct ClickEvent(pub Ray);
 
 #[derive(Event)]
-pub struct HoverEvent(pub Vec3);
+pub struct HoverEvent(pub Ray);
+
+/// a piece dropped in a gravity game, falling from above its column into place
+#[derive(Component)]
+struct Falling {
+    /// where the piece starts, relative to where it lands
+    from: Vec3,
+    /// how far the piece is currently moved from where it lands
+    offset: Vec3,
+    timer: Timer,
+}
+
+impl Falling {
+    fn new(rows: usize) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let from = Vec3::new(0.0, DROP_HEIGHT, -(rows as f32) * (TILE_SIZE + TILE_GAP));
+        Self {
+            from,
+            offset: Vec3::ZERO,
+            timer: Timer::from_seconds(DROP_SECONDS, TimerMode::Once),
+        }
+    }
+}
+
+/// where a piece sits at full size. Smaller gobblet pieces are scaled down from it.
+#[derive(Component)]
+struct FullSize(Transform);
 
 #[derive(Component, Default)]
-struct Pos {
-    x: usize,
-    y: usize,
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
 }
 
 #[derive(Component, Default, Clone)]
-enum Tile {
+pub enum Tile {
     Cross,
     Nought,
     #[default]
@@ -91,6 +155,35 @@ struct UnHighlighted {
     material: Handle<StandardMaterial>,
 }
 
+// playable tiles in games with small boards, where the boards that can be played change
+#[derive(Resource)]
+struct Active {
+    material: Handle<StandardMaterial>,
+}
+
+#[derive(Resource)]
+struct Winning {
+    material: Handle<StandardMaterial>,
+}
+
+// the completed line in games where it loses, such as misère and notakto
+#[derive(Resource)]
+struct Losing {
+    material: Handle<StandardMaterial>,
+}
+
+#[derive(Resource)]
+struct TileAssets {
+    board_mesh: Handle<Mesh>,
+    cross_mesh: Handle<Mesh>,
+    nought_mesh: Handle<Mesh>,
+    cross_material: Handle<StandardMaterial>,
+    nought_material: Handle<StandardMaterial>,
+    // the piece about to vanish in infinite games fades out
+    vanishing_cross_material: Handle<StandardMaterial>,
+    vanishing_nought_material: Handle<StandardMaterial>,
+}
+
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -98,68 +191,203 @@ fn setup(
 ) {
     let un_highlighted = materials.add(Color::BISQUE.into());
     let highlighted = materials.add(Color::ALICE_BLUE.into());
-    let cross_mat = materials.add(Color::RED.into());
-    let nought_mat = materials.add(Color::GREEN.into());
-
-    let board_mesh = meshes.add(shape::Plane::from_size(TILE_SIZE).into());
-    let cross_mesh = meshes.add(shape::Cylinder::default().into());
-    let nought_mesh = meshes.add(shape::Torus::default().into());
+    let active = materials.add(Color::AZURE.into());
+    let winning = materials.add(Color::GOLD.into());
+    let losing = materials.add(Color::GRAY.into());
 
     commands.insert_resource(UnHighlighted {
-        material: un_highlighted.clone(),
+        material: un_highlighted,
     });
     commands.insert_resource(Highlighted {
         material: highlighted,
     });
+    commands.insert_resource(Active { material: active });
+    commands.insert_resource(Winning { material: winning });
+    commands.insert_resource(Losing { material: losing });
+    commands.insert_resource(TileAssets {
+        board_mesh: meshes.add(shape::Plane::from_size(TILE_SIZE).into()),
+        cross_mesh: meshes.add(shape::Cylinder::default().into()),
+        nought_mesh: meshes.add(shape::Torus::default().into()),
+        cross_material: materials.add(Color::RED.into()),
+        nought_material: materials.add(Color::GREEN.into()),
+        vanishing_cross_material: materials.add(StandardMaterial {
+            alpha_mode: AlphaMode::Blend,
+            ..Color::RED.into()
+        }),
+        vanishing_nought_material: materials.add(StandardMaterial {
+            alpha_mode: AlphaMode::Blend,
+            ..Color::GREEN.into()
+        }),
+    });
+}
+
+/// whether the board is made of 3x3 small boards laid out together
+const fn has_sub_boards(board: &GameBoard) -> bool {
+    matches!(board, GameBoard::Ultimate(_) | GameBoard::Notakto(_))
+}
+
+/// whether completing the line lost the game rather than won it
+fn line_loses(game: &Game) -> bool {
+    match game.board {
+        // whoever kills the last board loses, whatever the rules
+        GameBoard::Notakto(_) => true,
+        // the roles decide who won, whatever the rules
+        GameBoard::OrderChaos(_) => false,
+        _ => game.rules == Rules::Misere,
+    }
+}
+
+/// the number of layers the board is stacked in. The layers are laid out side by side in the
+/// tiles of the game.
+pub const fn layers(board: &GameBoard) -> usize {
+    if matches!(board, GameBoard::Qubic(_)) {
+        QubicBoard::SIZE
+    } else {
+        1
+    }
+}
+
+/// spawns the tiles for the board, replacing them whenever the layout of the board changes
+fn spawn_tiles(
+    mut commands: Commands,
+    mut spawned: Local<Option<(usize, usize, bool, usize)>>,
+    tile_query: Query<Entity, With<Pos>>,
+    assets: Res<TileAssets>,
+    un_highlighted: Res<UnHighlighted>,
+    game_state: Res<GameState>,
+) {
+    let (width, height) = (game_state.board.width(), game_state.board.height());
+    let sub_boards = has_sub_boards(&game_state.game.board);
+    let layers = layers(&game_state.game.board);
+    if *spawned == Some((width, height, sub_boards, layers)) {
+        return;
+    }
+    *spawned = Some((width, height, sub_boards, layers));
+    let layer_width = width / layers;
+
+    for entity in &tile_query {
+        commands.entity(entity).despawn();
+    }
 
-    let start_offset = -TILE_SIZE - TILE_GAP;
-    for x in 0..3 {
-        for y in 0..3 {
+    // the distance of the nth tile from the first, with the small boards spaced apart
+    #[allow(clippy::cast_precision_loss)]
+    let distance = |n: usize| {
+        let sub_board_gap = if sub_boards {
+            (n / 3) as f32 * SUB_BOARD_GAP
+        } else {
+            0.0
+        };
+        (n as f32).mul_add(TILE_GAP + TILE_SIZE, sub_board_gap)
+    };
+
+    // centre the board on the origin, with any layers stacked above and below it
+    let (x_offset, z_offset) = (
+        distance(layer_width - 1) / -2.0,
+        distance(height - 1) / -2.0,
+    );
+    #[allow(clippy::cast_precision_loss)]
+    let height_of = |layer: usize| (layer as f32 - (layers - 1) as f32 / 2.0) * LAYER_GAP;
+
+    for x in 0..width {
+        for y in 0..height {
             let base = Transform {
-                #[allow(clippy::cast_precision_loss)]
                 translation: Vec3 {
-                    x: (x as f32).mul_add(TILE_GAP + TILE_SIZE, start_offset),
-                    y: 0.0,
-                    z: (y as f32).mul_add(TILE_GAP + TILE_SIZE, start_offset),
+                    x: distance(x % layer_width) + x_offset,
+                    y: height_of(x / layer_width),
+                    z: distance(y) + z_offset,
                 },
                 ..Default::default()
             };
 
             for tile in [Tile::Board, Tile::Cross, Tile::Nought] {
                 for transform in tile.transforms(&base) {
-                    commands.spawn(TileBundle {
-                        tile: tile.clone(),
-                        pos: Pos { x, y },
-                        mesh: match tile {
-                            Tile::Cross => cross_mesh.clone(),
-                            Tile::Nought => nought_mesh.clone(),
-                            Tile::Board => board_mesh.clone(),
+                    commands.spawn((
+                        TileBundle {
+                            tile: tile.clone(),
+                            pos: Pos { x, y },
+                            mesh: match tile {
+                                Tile::Cross => assets.cross_mesh.clone(),
+                                Tile::Nought => assets.nought_mesh.clone(),
+                                Tile::Board => assets.board_mesh.clone(),
+                            },
+                            material: match tile {
+                                Tile::Cross => assets.cross_material.clone(),
+                                Tile::Nought => assets.nought_material.clone(),
+                                Tile::Board => un_highlighted.material.clone(),
+                            },
+                            transform,
+                            visibility: Visibility::Hidden, // the board will be made visible on the first update.
+                            ..default()
                         },
-                        material: match tile {
-                            Tile::Cross => cross_mat.clone(),
-                            Tile::Nought => nought_mat.clone(),
-                            Tile::Board => un_highlighted.clone(),
-                        },
-                        transform,
-                        visibility: Visibility::Hidden, // the board will be made visible on the first update.
-                        ..default()
-                    });
+                        FullSize(transform),
+                    ));
                 }
             }
         }
     }
 }
 
+/// makes the tiles see-through when the board is stacked in layers, so every layer can be seen
+#[allow(clippy::too_many_arguments)] // bevy systems take their resources as arguments
+fn fade_layers(
+    mut faded: Local<Option<bool>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    highlighted: Res<Highlighted>,
+    un_highlighted: Res<UnHighlighted>,
+    active: Res<Active>,
+    winning: Res<Winning>,
+    losing: Res<Losing>,
+    game_state: Res<GameState>,
+) {
+    let layered = layers(&game_state.game.board) > 1;
+    if *faded == Some(layered) {
+        return;
+    }
+    *faded = Some(layered);
+
+    for handle in [
+        &highlighted.material,
+        &un_highlighted.material,
+        &active.material,
+        &winning.material,
+        &losing.material,
+    ] {
+        if let Some(material) = materials.get_mut(handle) {
+            material
+                .base_color
+                .set_a(if layered { LAYER_ALPHA } else { 1.0 });
+            material.alpha_mode = if layered {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            };
+            // the camera can orbit below the layers
+            material.double_sided = layered;
+            material.cull_mode = if layered { None } else { Some(Face::Back) };
+        }
+    }
+}
+
+// everything the update reads and changes about each tile
+type TileItem = (
+    Entity,
+    &'static Tile,
+    &'static Pos,
+    &'static GlobalTransform,
+    &'static mut Handle<StandardMaterial>,
+    &'static mut Visibility,
+);
+
+#[allow(clippy::too_many_arguments)] // bevy systems take their resources as arguments
 fn update(
-    mut tile_query: Query<(
-        &Tile,
-        &Pos,
-        &GlobalTransform,
-        &mut Handle<StandardMaterial>,
-        &mut Visibility,
-    )>,
+    mut commands: Commands,
+    mut tile_query: Query<TileItem>,
     highlighted: Res<Highlighted>,
     un_highlighted: Res<UnHighlighted>,
+    active: Res<Active>,
+    winning: Res<Winning>,
+    losing: Res<Losing>,
+    assets: Res<TileAssets>,
     mut ev_hover: EventReader<HoverEvent>,
     mut ev_click: EventReader<ClickEvent>,
     mut ev_client: EventWriter<ClientEvent>,
@@ -169,43 +397,87 @@ fn update(
     let hover = ev_hover.read().last();
     let click = ev_click.read().last();
 
-    let blocked = game_state.blocking_message.is_some();
+    // spectators can watch but not play
+    let blocked = game_state.blocking_message.is_some() || game_state.spectating;
+    let symbol = game_state.symbol();
+    // in gravity games a whole column is played at once
+    let gravity = matches!(game_state.game.board, GameBoard::Gravity(_));
 
-    for (tile, pos, transform, mut mat, mut visibility) in &mut tile_query {
+    // the playable tiles hovered over and clicked
+    let playable = |position| !blocked && game_state.playable(position);
+    let hovered = hover.and_then(|h| nearest_tile(&tile_query, h.0, gravity, playable));
+    let clicked = click.and_then(|c| nearest_tile(&tile_query, c.0, gravity, playable));
+
+    for (entity, tile, pos, _, mut mat, mut visibility) in &mut tile_query {
         let visible = match tile {
-            Tile::Cross => game_state.board.tiles[pos.x][pos.y].is_cross(),
-            Tile::Nought => game_state.board.tiles[pos.x][pos.y].is_nought(),
+            Tile::Cross | Tile::Nought => {
+                let vanishing = game_state.vanishing == (pos.x, pos.y).try_into().ok();
+                let material = match (tile, vanishing) {
+                    (Tile::Cross, false) => &assets.cross_material,
+                    (Tile::Cross, true) => &assets.vanishing_cross_material,
+                    (_, false) => &assets.nought_material,
+                    (_, true) => &assets.vanishing_nought_material,
+                };
+                if *mat != *material {
+                    *mat = material.clone();
+                }
+
+                let played = game_state.board.tiles[pos.x][pos.y];
+                if matches!(tile, Tile::Cross) {
+                    played.is_cross()
+                } else {
+                    played.is_nought()
+                }
+            }
             Tile::Board => {
-                // the center of the tile
-                let centre = transform.translation();
-
-                if let Some(hover) = hover.map(|h| h.0) {
-                    if !blocked
-                        && game_state.board.tiles[pos.x][pos.y].is_unplayed()
-                        && (hover.x - centre.x).abs() < TILE_SIZE / 2.0
-                        && (hover.z - centre.z).abs() < TILE_SIZE / 2.0
-                    {
+                let position: Position = (pos.x, pos.y).try_into().unwrap();
+                let playable = !blocked && game_state.playable(position);
+
+                let in_winning_line = game_state
+                    .winning_line
+                    .as_ref()
+                    .is_some_and(|line| line.contains(&position));
+
+                // the material when the tile isn't hovered over. The playable tiles are shown when
+                // they change each move, and when a cycle is being collapsed.
+                let shown = has_sub_boards(&game_state.game.board)
+                    || game_state.game.pending_collapse().is_some();
+                let picked = game_state.quantum_first == Some(position)
+                    || game_state.gobblet_from == Some(position);
+                let resting = if (playable && shown) || picked {
+                    &active.material
+                } else {
+                    &un_highlighted.material
+                };
+
+                if in_winning_line {
+                    *mat = if line_loses(&game_state.game) {
+                        losing.material.clone()
+                    } else {
+                        winning.material.clone()
+                    };
+                } else if hover.is_some() {
+                    if hovered == Some(entity) {
                         *mat = highlighted.material.clone();
                     } else {
-                        *mat = un_highlighted.material.clone();
+                        *mat = resting.clone();
                     }
+                } else if *mat != highlighted.material || !playable {
+                    // the winning line is cleared when a rematch starts, and the playable tiles
+                    // change each move
+                    *mat = resting.clone();
                 }
 
-                if !blocked && game_state.board.tiles[pos.x][pos.y].is_unplayed() {
-                    if let Some(click) = click.map(|c| c.0) {
-                        if (click.x - centre.x).abs() < TILE_SIZE / 2.0
-                            && (click.z - centre.z).abs() < TILE_SIZE / 2.0
-                        {
-                            ev_client.send(
-                                ClientMessage::Move((pos.x, pos.y).try_into().unwrap()).into(),
-                            );
-                        }
+                if clicked == Some(entity) {
+                    if let Some(message) = click_message(&mut game_state, position, symbol) {
+                        ev_client.send(message.into());
                     }
                 }
 
                 // logic to request rematch
                 if blocked
-                    && game_state.board.summary().is_finished()
+                    && !game_state.spectating
+                    && game_state.game.summary().is_finished()
                     && !game_state.ended
                     && !game_state.rematch_requested
                     && click.is_some()
@@ -220,6 +492,10 @@ fn update(
             }
         };
 
+        if gravity && visible && *visibility == Visibility::Hidden {
+            commands.entity(entity).insert(Falling::new(pos.y + 1));
+        }
+
         *visibility = if visible {
             Visibility::Inherited
         } else {
@@ -227,3 +503,184 @@ fn update(
         };
     }
 }
+
+/// the tile nearest the camera that the ray passes over, out of those that pass the filter. Layers
+/// are stacked, so the ray can pass over a tile on each of them. In gravity games a tile only has to
+/// be in the same column.
+fn nearest_tile(
+    tile_query: &Query<TileItem>,
+    ray: Ray,
+    gravity: bool,
+    filter: impl Fn(Position) -> bool,
+) -> Option<Entity> {
+    tile_query
+        .iter()
+        .filter(|(_, tile, pos, ..)| {
+            matches!(tile, Tile::Board) && Position::try_from((pos.x, pos.y)).is_ok_and(&filter)
+        })
+        .filter_map(|(entity, _, _, transform, ..)| {
+            let centre = transform.translation();
+            let distance = ray.intersect_plane(centre, Vec3::Y)?;
+            let point = ray.get_point(distance);
+            let over = (point.x - centre.x).abs() < TILE_SIZE / 2.0
+                && (gravity || (point.z - centre.z).abs() < TILE_SIZE / 2.0);
+            over.then_some((entity, distance))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// the message sent when a playable tile is clicked. Quantum moves and moving a gobblet piece take
+/// two clicks, so the first only picks the cell.
+fn click_message(
+    game_state: &mut GameState,
+    position: Position,
+    symbol: noughts_and_crosses_core::Tile,
+) -> Option<ClientMessage> {
+    let game = &game_state.game;
+    Some(match &game.board {
+        GameBoard::Quantum(_) if game.pending_collapse().is_some() => {
+            ClientMessage::Collapse(position)
+        }
+        GameBoard::Quantum(_) => match game_state.quantum_first.take() {
+            // the last cell is played classically, with a single click
+            None if game.legal_moves().len() == 1 => ClientMessage::Entangle(position, position),
+            None => {
+                game_state.quantum_first = Some(position);
+                return None;
+            }
+            // clicking the first cell again picks it back up
+            Some(first) if first == position => return None,
+            Some(first) => ClientMessage::Entangle(first, position),
+        },
+        GameBoard::Gobblet(board) => {
+            match (game_state.gobblet_pick, game_state.gobblet_from.take()) {
+                (GobbletPick::New(size), _) => ClientMessage::Gobble(GobbletMove {
+                    size,
+                    from: None,
+                    to: position,
+                }),
+                (GobbletPick::Move, None) => {
+                    game_state.gobblet_from = Some(position);
+                    return None;
+                }
+                // clicking the piece again puts it back down
+                (GobbletPick::Move, Some(from)) if from == position => return None,
+                (GobbletPick::Move, Some(from)) => ClientMessage::Gobble(GobbletMove {
+                    size: board.top(from)?.size,
+                    from: Some(from),
+                    to: position,
+                }),
+            }
+        }
+        GameBoard::Numerical(_) => ClientMessage::Number(position, game_state.number()?),
+        GameBoard::Gravity(_) if game.rules != Rules::Wild => {
+            ClientMessage::Drop(Column::from(position))
+        }
+        _ if game_state.picks_symbol() => ClientMessage::Place(position, symbol),
+        _ => ClientMessage::Move(position),
+    })
+}
+
+// the tiles that labels are written over
+pub type LabelledTiles<'w, 's> =
+    Query<'w, 's, (&'static Pos, &'static Tile, &'static GlobalTransform)>;
+
+/// where the far left corner of the tile is on the screen, so a label written there doesn't cover
+/// the piece
+pub fn tile_corner(
+    (camera, camera_transform): (&Camera, &GlobalTransform),
+    tiles: &LabelledTiles,
+    x: usize,
+    y: usize,
+) -> Option<Vec2> {
+    tiles
+        .iter()
+        .find(|(pos, tile, _)| pos.x == x && pos.y == y && matches!(tile, Tile::Board))
+        .and_then(|(_, _, transform)| {
+            let corner = transform.translation() - Vec3::new(TILE_SIZE, 0.0, TILE_SIZE) / 2.0;
+            camera.world_to_viewport(camera_transform, corner)
+        })
+}
+
+/// fades out the piece that vanishes on the next move in infinite games, starting again whenever
+/// a different piece is about to vanish
+fn fade_vanishing(
+    mut faded: Local<Option<Position>>,
+    mut timer: Local<Timer>,
+    time: Res<Time>,
+    assets: Res<TileAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    game_state: Res<GameState>,
+) {
+    if *faded != game_state.vanishing {
+        *faded = game_state.vanishing;
+        *timer = Timer::from_seconds(FADE_SECONDS, TimerMode::Once);
+    }
+    timer.tick(time.delta());
+    if timer.finished() && !timer.just_finished() {
+        return;
+    }
+
+    let alpha = (1.0 - VANISHING_ALPHA).mul_add(-timer.percent(), 1.0);
+    for handle in [
+        &assets.vanishing_cross_material,
+        &assets.vanishing_nought_material,
+    ] {
+        if let Some(material) = materials.get_mut(handle) {
+            material.base_color.set_a(alpha);
+        }
+    }
+}
+
+/// shrinks the pieces on top of gobblet stacks to their size
+fn scale_pieces(
+    game_state: Res<GameState>,
+    mut query: Query<(&Tile, &Pos, &FullSize, &mut Transform)>,
+) {
+    let GameBoard::Gobblet(board) = &game_state.game.board else {
+        return;
+    };
+    if !game_state.is_changed() {
+        return;
+    }
+
+    for (tile, pos, full_size, mut transform) in &mut query {
+        if matches!(tile, Tile::Board) {
+            continue;
+        }
+        let scale = Position::try_from((pos.x, pos.y))
+            .ok()
+            .and_then(|cell| board.top(cell))
+            .map_or(1.0, |piece| match piece.size {
+                Size::Small => SMALL_SCALE,
+                Size::Medium => MEDIUM_SCALE,
+                Size::Large => 1.0,
+            });
+        // the board is level with the origin, so scaling the height keeps pieces resting on it
+        *transform = Transform {
+            translation: full_size.0.translation * Vec3::new(1.0, scale, 1.0),
+            scale: full_size.0.scale * scale,
+            ..full_size.0
+        };
+    }
+}
+
+/// moves falling pieces towards where they land, speeding up as they fall
+fn fall(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut Falling)>,
+) {
+    for (entity, mut transform, mut falling) in &mut query {
+        falling.timer.tick(time.delta());
+        let t = falling.timer.percent();
+        let offset = falling.from * t.mul_add(-t, 1.0);
+        transform.translation += offset - falling.offset;
+        falling.offset = offset;
+
+        if falling.timer.finished() {
+            commands.entity(entity).remove::<Falling>();
+        }
+    }
+}
@@ -1,28 +1,178 @@
-use bevy::prelude::*;
+use bevy::{
+    input::mouse::{MouseMotion, MouseWheel},
+    prelude::*,
+};
+
+use crate::{board, game_state::GameState};
 
 pub struct Plugin;
 
 impl bevy::app::Plugin for Plugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup);
+        app.add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (fit_board, zoom_and_pan, orbit, place_camera).chain(),
+            )
+            .init_resource::<View>();
+    }
+}
+
+// the scene is laid out for a 3x3 board and scaled up for larger ones
+const CAMERA_POSITION: Vec3 = Vec3::new(0.0, 16.0, 12.0);
+const CAMERA_TARGET: Vec3 = Vec3::new(0.0, 0.0, 2.0);
+const LIGHT_POSITION: Vec3 = Vec3::new(8.0, 16.0, 8.0);
+const LIGHT_INTENSITY: f32 = 9000.0;
+const LIGHT_RANGE: f32 = 100.0;
+// how close the camera can get, as a fraction of the distance that fits the whole board
+const MIN_ZOOM: f32 = 0.25;
+const ZOOM_SPEED: f32 = 0.1;
+// the distance panned each second, for a 3x3 board
+const PAN_SPEED: f32 = 12.0;
+// about half the width of a 3x3 board
+const PAN_LIMIT: f32 = 8.0;
+// how far the view turns for each pixel the mouse is dragged
+const ORBIT_SPEED: f32 = 0.005;
+// how far the camera can tilt up or down from where it starts, keeping it above the board
+const MAX_TILT: f32 = 0.45;
+// boards stacked in layers are taller, so the camera starts further back
+const LAYERED_SCALE: f32 = 1.5;
+
+/// where the camera is looking. Large boards can be zoomed with the mouse wheel and panned with
+/// the arrow keys, and the camera orbits the board when dragged with the right mouse button.
+#[derive(Resource)]
+struct View {
+    /// how much larger than a 3x3 board the board is
+    scale: f32,
+    zoom: f32,
+    pan: Vec3,
+    /// how far the camera has turned around the board and tilted towards it, in radians
+    orbit: Vec2,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            zoom: 1.0,
+            pan: Vec3::ZERO,
+            orbit: Vec2::ZERO,
+        }
     }
 }
 
 fn setup(mut commands: Commands) {
     commands.spawn(PointLightBundle {
         point_light: PointLight {
-            intensity: 9000.0,
-            range: 100.0,
+            intensity: LIGHT_INTENSITY,
+            range: LIGHT_RANGE,
             shadows_enabled: true,
             ..default()
         },
-        transform: Transform::from_xyz(8.0, 16.0, 8.0),
+        transform: Transform::from_translation(LIGHT_POSITION),
         ..default()
     });
 
     commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(0.0, 16.0, 12.0)
-            .looking_at(Vec3::new(0.0, 0.0, 2.0), Vec3::Y),
+        transform: Transform::from_translation(CAMERA_POSITION).looking_at(CAMERA_TARGET, Vec3::Y),
         ..default()
     });
 }
+
+/// moves the light back far enough to light the whole board, and resets the view to fit it
+fn fit_board(
+    mut fitted: Local<Option<(usize, usize)>>,
+    mut light_query: Query<(&mut Transform, &mut PointLight), Without<Camera>>,
+    mut view: ResMut<View>,
+    game_state: Res<GameState>,
+) {
+    let layers = board::layers(&game_state.game.board);
+    let side = (game_state.board.width() / layers).max(game_state.board.height());
+    if *fitted == Some((side, layers)) {
+        return;
+    }
+    *fitted = Some((side, layers));
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut scale = side.max(3) as f32 / 3.0;
+    if layers > 1 {
+        scale *= LAYERED_SCALE;
+    }
+    *view = View { scale, ..default() };
+
+    for (mut transform, mut light) in &mut light_query {
+        transform.translation = LIGHT_POSITION * scale;
+        light.range = LIGHT_RANGE * scale;
+        light.intensity = LIGHT_INTENSITY * scale * scale;
+    }
+}
+
+/// zooms with the mouse wheel and pans with the arrow keys, keeping the board in view
+fn zoom_and_pan(
+    mut ev_wheel: EventReader<MouseWheel>,
+    keys: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut view: ResMut<View>,
+) {
+    for ev in ev_wheel.read() {
+        view.zoom = ZOOM_SPEED
+            .mul_add(-ev.y.signum(), view.zoom)
+            .clamp(MIN_ZOOM, 1.0);
+    }
+
+    let mut direction = Vec3::ZERO;
+    for (key, step) in [
+        (KeyCode::Left, Vec3::NEG_X),
+        (KeyCode::Right, Vec3::X),
+        (KeyCode::Up, Vec3::NEG_Z),
+        (KeyCode::Down, Vec3::Z),
+    ] {
+        if keys.pressed(key) {
+            direction += step;
+        }
+    }
+    if direction != Vec3::ZERO {
+        // pan relative to the way the camera is facing
+        let direction = Quat::from_rotation_y(view.orbit.x) * direction;
+        let limit = PAN_LIMIT * view.scale;
+        view.pan = (view.pan + direction * PAN_SPEED * view.scale * time.delta_seconds())
+            .clamp(Vec3::splat(-limit), Vec3::splat(limit));
+    }
+}
+
+/// turns the camera around the board and tilts it while the right mouse button is dragged
+fn orbit(
+    mut ev_motion: EventReader<MouseMotion>,
+    buttons: Res<Input<MouseButton>>,
+    mut view: ResMut<View>,
+) {
+    if !buttons.pressed(MouseButton::Right) {
+        ev_motion.clear();
+        return;
+    }
+    let delta: Vec2 = ev_motion.read().map(|ev| ev.delta).sum();
+    if delta != Vec2::ZERO {
+        view.orbit.x = delta.x.mul_add(-ORBIT_SPEED, view.orbit.x);
+        view.orbit.y = delta
+            .y
+            .mul_add(ORBIT_SPEED, view.orbit.y)
+            .clamp(-MAX_TILT, MAX_TILT);
+    }
+}
+
+fn place_camera(
+    view: Res<View>,
+    mut camera_query: Query<&mut Transform, (With<Camera>, Without<PointLight>)>,
+) {
+    if !view.is_changed() {
+        return;
+    }
+    // scaling the position keeps the camera pointing at the middle of the board
+    let distance = view.scale * view.zoom;
+    let target = CAMERA_TARGET * distance + view.pan;
+    let orbit = Quat::from_rotation_y(view.orbit.x) * Quat::from_rotation_x(view.orbit.y);
+    let position = target + orbit * (CAMERA_POSITION - CAMERA_TARGET) * distance;
+    for mut transform in &mut camera_query {
+        *transform = Transform::from_translation(position).looking_at(target, Vec3::Y);
+    }
+}
@@ -2,10 +2,11 @@ use bevy::prelude::*;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 use wasm_bindgen_futures::js_sys::Uint8Array;
-use web_sys::{ErrorEvent, MessageEvent, WebSocket};
+use web_sys::{ErrorEvent, Event, MessageEvent, WebSocket};
 
 use noughts_and_crosses_core::{
-    game_state::GameID,
+    bot::Difficulty,
+    game_state::{GameID, SeatToken},
     messages::{ClientMessage, ServerMessage},
 };
 
@@ -52,7 +53,7 @@ fn setup(mut commands: Commands) {
     let (server_sender, server_receiver) = mpsc::unbounded_channel();
     commands.insert_resource(MessageReceiver(server_receiver));
     let (client_sender, mut client_receiver) = mpsc::unbounded_channel();
-    commands.insert_resource(MessageSender(client_sender));
+    commands.insert_resource(MessageSender(client_sender.clone()));
 
     // get or set the game id
     let window = web_sys::window().unwrap();
@@ -60,10 +61,19 @@ fn setup(mut commands: Commands) {
         |_| GameID::new(),
         |pathname| {
             GameID::try_from(pathname.strip_prefix('/').unwrap_or(""))
-                .map_or_else(|_| GameID::new(), |game_id| game_id)
+                .unwrap_or_else(|_| GameID::new())
         },
     );
 
+    // a computer opponent can be requested with ?bot=<difficulty>
+    let mut computer = window.location().search().ok().and_then(|search| {
+        search
+            .trim_start_matches('?')
+            .split('&')
+            .find_map(|param| param.strip_prefix("bot="))
+            .and_then(|difficulty| Difficulty::try_from(difficulty).ok())
+    });
+
     // update the URL to the current game
     if let Ok(pathname) = window.location().pathname() {
         let desired = format!("/{game_id}");
@@ -76,16 +86,51 @@ fn setup(mut commands: Commands) {
         }
     }
 
-    let ws = WebSocket::new(&format!("ws://127.0.0.1:8787/game/{game_id}"))
+    // options for a new game are passed on to the server in the query string
+    let search = window.location().search().unwrap_or_default();
+    let ws = WebSocket::new(&format!("ws://127.0.0.1:8787/game/{game_id}{search}"))
         .expect("failed to open connection to server");
     ws.set_binary_type(web_sys::BinaryType::Arraybuffer);
 
+    // the seat token is kept in session storage so refreshing the page reclaims the seat
+    let seat_key = format!("seat-{game_id}");
+    let session_storage = window.session_storage().ok().flatten();
+
+    // once connected, reclaim our seat if we have one, otherwise join the game
+    let storage = session_storage.clone();
+    let key = seat_key.clone();
+    let client_sen = client_sender.clone();
+    let onopen_callback = Closure::wrap(Box::new(move |_: Event| {
+        let token = storage
+            .as_ref()
+            .and_then(|storage| storage.get_item(&key).ok().flatten())
+            .and_then(|token| SeatToken::try_from(token.as_str()).ok());
+        client_sen
+            .send(token.map_or(ClientMessage::Join, ClientMessage::Reclaim))
+            .unwrap();
+    }) as Box<dyn FnMut(Event)>);
+    ws.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
+    onopen_callback.forget();
+
     // when a message is received, pass it to the server_sender channel
     let sen = server_sender.clone();
     let onmessage_callback = Closure::wrap(Box::new(move |e: MessageEvent| {
         if let Ok(abuf) = e.data().dyn_into() {
             let bytes = Uint8Array::new(&abuf).to_vec();
             let message: ServerMessage = bincode::deserialize(&bytes).unwrap();
+            if let (ServerMessage::Seated(seat), Some(storage)) = (&message, &session_storage) {
+                storage
+                    .set_item(&seat_key, &seat.token.to_string())
+                    .unwrap_or_default();
+            }
+            // the computer is only requested once, since waiting is sent again on reconnecting
+            if matches!(message, ServerMessage::WaitingForOpponentYouAreCrosses) {
+                if let Some(difficulty) = computer.take() {
+                    client_sender
+                        .send(ClientMessage::PlayComputer(difficulty))
+                        .unwrap();
+                }
+            }
             sen.send(message).unwrap();
         }
     }) as Box<dyn FnMut(MessageEvent)>);
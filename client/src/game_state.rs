@@ -1,5 +1,12 @@
 use bevy::prelude::*;
-use noughts_and_crosses_core::{messages::ServerMessage, Board};
+use noughts_and_crosses_core::{
+    game::{Game, GameBoard},
+    game_state::Position,
+    gobblet::{GobbletMove, Size},
+    messages::ServerMessage,
+    order_chaos::Role,
+    Board, GameSummary, Rules, Tile,
+};
 
 use crate::client::ServerEvent;
 
@@ -7,6 +14,10 @@ pub struct Plugin;
 
 const WIN_MESSAGE: &str = "You won! Click to request a rematch.";
 const LOST_MESSAGE: &str = "You lost. Click to request a rematch.";
+// in misère and notakto games the line is completed by the loser
+const MISERE_WIN_MESSAGE: &str =
+    "Your opponent completed a line. You won! Click to request a rematch.";
+const MISERE_LOST_MESSAGE: &str = "You completed a line, so you lost. Click to request a rematch.";
 
 impl bevy::app::Plugin for Plugin {
     fn build(&self, app: &mut App) {
@@ -14,17 +25,134 @@ impl bevy::app::Plugin for Plugin {
     }
 }
 
+/// what clicking the board does in gobblet games
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GobbletPick {
+    /// play a new piece of the size
+    New(Size),
+    /// pick up one of your pieces on the board, then put it down somewhere else
+    Move,
+}
+
+impl Default for GobbletPick {
+    fn default() -> Self {
+        Self::New(Size::Large)
+    }
+}
+
 #[derive(Resource, Default)]
 #[allow(clippy::struct_excessive_bools)] // todo
 pub struct GameState {
+    pub game: Game,
+    /// every tile of the game laid out for display
     pub board: Board,
+    pub winning_line: Option<Vec<Position>>,
+    /// the piece removed by the next move, in infinite games
+    pub vanishing: Option<Position>,
     pub turn: bool,
+    pub crosses_turn: bool,
     pub is_crosses: bool,
+    /// the symbol picked to place in games where either can be placed
+    pub wild_symbol: Option<Tile>,
+    /// the first cell picked for a quantum move, waiting for the second
+    pub quantum_first: Option<Position>,
+    pub gobblet_pick: GobbletPick,
+    /// the piece picked up to move in gobblet games, waiting for where it goes
+    pub gobblet_from: Option<Position>,
+    /// the number picked to play in numerical games
+    pub picked_number: Option<u8>,
+    pub spectating: bool,
     pub blocking_message: Option<String>,
     pub rematch_requested: bool,
     pub ended: bool,
 }
 
+impl GameState {
+    pub const fn player(&self) -> Tile {
+        if self.is_crosses {
+            Tile::Cross
+        } else {
+            Tile::Nought
+        }
+    }
+
+    /// your side in order and chaos, which replaces playing crosses or noughts
+    pub fn role(&self) -> Option<Role> {
+        self.game.role(self.player())
+    }
+
+    /// whether you pick the symbol you place, in wild and order and chaos games
+    pub const fn picks_symbol(&self) -> bool {
+        self.game.symbols(self.player()).len() > 1
+    }
+
+    /// the symbol placed by clicking a tile. When either can be placed this is the picked symbol,
+    /// which is your own until another is picked. In notakto it is always a cross.
+    pub fn symbol(&self) -> Tile {
+        match self.wild_symbol {
+            Some(symbol) if self.picks_symbol() => symbol,
+            _ => self.game.default_symbol(self.player()),
+        }
+    }
+
+    /// your numbers yet to be played in numerical games, smallest first
+    pub fn numbers(&self) -> Vec<u8> {
+        match &self.game.board {
+            GameBoard::Numerical(board) => board.remaining(self.player()),
+            _ => Vec::new(),
+        }
+    }
+
+    /// the number played by clicking a tile in numerical games. This is the picked number, or
+    /// your smallest until one is picked.
+    pub fn number(&self) -> Option<u8> {
+        let numbers = self.numbers();
+        self.picked_number
+            .filter(|number| numbers.contains(number))
+            .or_else(|| numbers.first().copied())
+    }
+
+    /// whether clicking the position makes a move, or starts one in games that take two clicks
+    pub fn playable(&self, position: Position) -> bool {
+        if let GameBoard::Numerical(_) = &self.game.board {
+            return self.number().is_some_and(|number| {
+                self.game
+                    .check_number(position, number, self.player())
+                    .is_ok()
+            });
+        }
+        let GameBoard::Gobblet(board) = &self.game.board else {
+            return self.game.check_placement(position, self.symbol()).is_ok();
+        };
+        let m = match (self.gobblet_pick, self.gobblet_from) {
+            (GobbletPick::New(size), _) => GobbletMove {
+                size,
+                from: None,
+                to: position,
+            },
+            // the piece picked up can be put back down
+            (GobbletPick::Move, Some(from)) if from == position => return true,
+            (GobbletPick::Move, Some(from)) => match board.top(from) {
+                Some(piece) => GobbletMove {
+                    size: piece.size,
+                    from: Some(from),
+                    to: position,
+                },
+                None => return false,
+            },
+            // any of your pieces that can go somewhere can be picked up
+            (GobbletPick::Move, None) => {
+                return self
+                    .game
+                    .gobblet_moves(self.player())
+                    .iter()
+                    .any(|m| m.from == Some(position));
+            }
+        };
+        self.game.check_gobblet(m, self.player()).is_ok()
+    }
+}
+
 fn setup(mut commands: Commands) {
     // start the game in a loading state. This will transition once the websocket is established and the first message is received
     let game_state = GameState {
@@ -38,19 +166,49 @@ fn update(mut ev_server: EventReader<ServerEvent>, mut game_state: ResMut<GameSt
     for ev in ev_server.read() {
         match &ev.message {
             ServerMessage::Error(e) => {
-                game_state.blocking_message = Some(e.to_string());
+                game_state.blocking_message = Some(e.clone());
                 game_state.ended = true; // lock the game up as if the opponent left
             }
+            ServerMessage::Seated(seat) => {
+                game_state.is_crosses = seat.crosses;
+            }
+            ServerMessage::Spectating => {
+                game_state.spectating = true;
+                game_state.blocking_message = None;
+            }
             ServerMessage::WaitingForOpponentYouAreCrosses => {
                 game_state.is_crosses = true;
                 game_state.blocking_message =
                     Some("Waiting for Opponent. Send the URL to a friend".to_string());
             }
             ServerMessage::GameUpdate(update) => {
-                game_state.board = update.board;
+                if let Err(e) = update.game.validate() {
+                    game_state.blocking_message = Some(format!("Received an impossible game: {e}"));
+                    game_state.ended = true;
+                    continue;
+                }
+                game_state.game.clone_from(&update.game);
+                game_state.quantum_first = None;
+                game_state.gobblet_from = None;
+                game_state.board = update.game.tiles();
+                game_state.winning_line.clone_from(&update.winning_line);
+                game_state.vanishing = update.vanishing;
                 game_state.turn = update.turn;
+                game_state.crosses_turn = update.crosses_turn;
                 game_state.rematch_requested = false;
-                match update.board.summary() {
+                if game_state.spectating {
+                    game_state.blocking_message = spectator_message(&update.game);
+                    continue;
+                }
+                let (win_message, lost_message) = match (&update.game.board, update.game.rules) {
+                    // the roles decide who won, whatever the rules
+                    (GameBoard::OrderChaos(_), _) => (WIN_MESSAGE, LOST_MESSAGE),
+                    (GameBoard::Notakto(_), _) | (_, Rules::Misere) => {
+                        (MISERE_WIN_MESSAGE, MISERE_LOST_MESSAGE)
+                    }
+                    (_, Rules::Normal | Rules::Wild) => (WIN_MESSAGE, LOST_MESSAGE),
+                };
+                match update.game.summary() {
                     noughts_and_crosses_core::GameSummary::InProgress => {
                         game_state.blocking_message = if update.turn {
                             None
@@ -61,9 +219,9 @@ fn update(mut ev_server: EventReader<ServerEvent>, mut game_state: ResMut<GameSt
                     noughts_and_crosses_core::GameSummary::NoughtWin => {
                         game_state.blocking_message = Some(
                             if game_state.is_crosses {
-                                LOST_MESSAGE
+                                lost_message
                             } else {
-                                WIN_MESSAGE
+                                win_message
                             }
                             .to_string(),
                         );
@@ -71,9 +229,9 @@ fn update(mut ev_server: EventReader<ServerEvent>, mut game_state: ResMut<GameSt
                     noughts_and_crosses_core::GameSummary::CrossWin => {
                         game_state.blocking_message = Some(
                             if game_state.is_crosses {
-                                WIN_MESSAGE
+                                win_message
                             } else {
-                                LOST_MESSAGE
+                                lost_message
                             }
                             .to_string(),
                         );
@@ -87,8 +245,20 @@ fn update(mut ev_server: EventReader<ServerEvent>, mut game_state: ResMut<GameSt
                 game_state.blocking_message =
                     Some("Your opponent has requested a rematch. Click to oblige.".to_string());
             }
+            ServerMessage::OpponentDisconnected => {
+                game_state.blocking_message = Some(
+                    "Your opponent disconnected. Waiting for them to reconnect...".to_string(),
+                );
+            }
             ServerMessage::GameEnded => {
-                game_state.blocking_message = Some("Your opponent has left".to_string());
+                game_state.blocking_message = Some(
+                    if game_state.spectating {
+                        "A player has left"
+                    } else {
+                        "Your opponent has left"
+                    }
+                    .to_string(),
+                );
                 game_state.ended = true;
 
                 // TODO, option to start new game
@@ -96,3 +266,17 @@ fn update(mut ev_server: EventReader<ServerEvent>, mut game_state: ResMut<GameSt
         }
     }
 }
+
+/// spectators are told the result without being offered a rematch
+fn spectator_message(game: &Game) -> Option<String> {
+    let summary = game.summary();
+    let role = summary.winner().and_then(|winner| game.role(winner));
+    match (summary, role) {
+        (GameSummary::InProgress, _) => None,
+        (_, Some(Role::Order)) => Some("Order won.".to_string()),
+        (_, Some(Role::Chaos)) => Some("Chaos won.".to_string()),
+        (GameSummary::NoughtWin, None) => Some("Noughts won.".to_string()),
+        (GameSummary::CrossWin, None) => Some("Crosses won.".to_string()),
+        (GameSummary::Tie, None) => Some("It is a draw".to_string()),
+    }
+}
@@ -17,6 +17,7 @@ fn update(
     mut ev_click: EventWriter<ClickEvent>,
     mut ev_hover: EventWriter<HoverEvent>,
     mut touch_evr: EventReader<TouchInput>,
+    ui_buttons: Query<&Interaction, With<Button>>,
 ) {
     let (camera, camera_global_transform) = camera_query.single();
     let window = window_query.single();
@@ -40,20 +41,19 @@ fn update(
     // process hover
     if let Some(pos) = window.cursor_position() {
         if let Some(ray) = camera.viewport_to_world(camera_global_transform, pos) {
-            if let Some(distance) = ray.intersect_plane(Vec3::ZERO, Vec3::Y) {
-                let point = ray.get_point(distance);
-                ev_hover.send(HoverEvent(point));
-            }
+            ev_hover.send(HoverEvent(ray));
         }
     }
 
+    // clicks on buttons aren't meant for the board underneath
+    if ui_buttons.iter().any(|i| *i != Interaction::None) {
+        click_position = None;
+    }
+
     // process the latest touch or mouse click
     if let Some(pos) = click_position {
         if let Some(ray) = camera.viewport_to_world(camera_global_transform, pos) {
-            if let Some(distance) = ray.intersect_plane(Vec3::ZERO, Vec3::Y) {
-                let point = ray.get_point(distance);
-                ev_click.send(ClickEvent(point));
-            }
+            ev_click.send(ClickEvent(ray));
         }
     }
 }
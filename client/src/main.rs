@@ -7,6 +7,10 @@ mod client;
 mod game_state;
 mod input;
 mod messages;
+mod numbers;
+mod quantum;
+mod size_picker;
+mod symbol_picker;
 
 use bevy::prelude::*;
 
@@ -25,5 +29,9 @@ fn main() {
         .add_plugins(game_state::Plugin)
         .add_plugins(input::Plugin)
         .add_plugins(messages::Plugin)
+        .add_plugins(numbers::Plugin)
+        .add_plugins(quantum::Plugin)
+        .add_plugins(size_picker::Plugin)
+        .add_plugins(symbol_picker::Plugin)
         .run();
 }
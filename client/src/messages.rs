@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use noughts_and_crosses_core::{game::GameBoard, order_chaos::Role, Rules, Tile};
 
 use crate::game_state::GameState;
 
@@ -61,12 +62,79 @@ fn update(game_state: Res<GameState>, mut query: Query<(&mut Text, &UiText)>) {
                 text.sections[0].value = game_state.blocking_message.clone().unwrap_or_default();
             }
             UiText::Turn => {
-                text.sections[0].value = if game_state.blocking_message.is_some() {
+                text.sections[0].value = if game_state.spectating {
+                    let to_move = if game_state.crosses_turn {
+                        Tile::Cross
+                    } else {
+                        Tile::Nought
+                    };
+                    if game_state.game.summary().is_finished() {
+                        "Spectating."
+                    } else if let Some(role) = game_state.game.role(to_move) {
+                        match role {
+                            Role::Order => "Spectating. Order to move.",
+                            Role::Chaos => "Spectating. Chaos to move.",
+                        }
+                    } else if game_state.crosses_turn {
+                        "Spectating. Crosses to move."
+                    } else {
+                        "Spectating. Noughts to move."
+                    }
+                } else if game_state.blocking_message.is_some() {
                     ""
-                } else if game_state.is_crosses {
-                    "Your turn. You are crosses."
                 } else {
-                    "Your turn. You are noughts."
+                    match (game_state.is_crosses, game_state.game.rules) {
+                        _ if matches!(game_state.game.board, GameBoard::Notakto(_)) => {
+                            "Your turn. Don't complete a line on the last board."
+                        }
+                        _ if game_state.game.pending_collapse().is_some() => {
+                            "Your opponent made a cycle. Pick where their last move collapses."
+                        }
+                        _ if game_state.quantum_first.is_some() => {
+                            "Pick a second cell to entangle with the first."
+                        }
+                        _ if matches!(game_state.game.board, GameBoard::Quantum(_)) => {
+                            "Your turn. Pick two cells to place spooky marks in."
+                        }
+                        _ if game_state.gobblet_from.is_some() => {
+                            "Pick where to move the piece, or click it again to put it back."
+                        }
+                        _ if matches!(game_state.game.board, GameBoard::Gobblet(_)) => {
+                            "Your turn. Pick a size with 1, 2 or 3, or M to move a piece on the \
+                             board."
+                        }
+                        _ if matches!(
+                            &game_state.game.board,
+                            GameBoard::Numerical(board) if board.odd == game_state.player()
+                        ) =>
+                        {
+                            "Your turn. You play the odd numbers. Pick one with the keys 1 to 9, \
+                             then make a line adding up to 15."
+                        }
+                        _ if matches!(game_state.game.board, GameBoard::Numerical(_)) => {
+                            "Your turn. You play the even numbers. Pick one with the keys 1 to 9, \
+                             then make a line adding up to 15."
+                        }
+                        _ if game_state.role() == Some(Role::Order) => {
+                            "Your turn. You are order: make five in a row of either symbol. Pick \
+                             one with X or O."
+                        }
+                        _ if game_state.role() == Some(Role::Chaos) => {
+                            "Your turn. You are chaos: fill the board without five in a row. Pick \
+                             a symbol with X or O."
+                        }
+                        (true, Rules::Normal) => "Your turn. You are crosses.",
+                        (false, Rules::Normal) => "Your turn. You are noughts.",
+                        (true, Rules::Misere) => {
+                            "Your turn. You are crosses. Don't complete a line."
+                        }
+                        (false, Rules::Misere) => {
+                            "Your turn. You are noughts. Don't complete a line."
+                        }
+                        (_, Rules::Wild) => {
+                            "Your turn. Pick a symbol with X or O, then complete a line to win."
+                        }
+                    }
                 }
                 .to_string();
             }
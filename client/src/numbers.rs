@@ -0,0 +1,279 @@
+use bevy::prelude::*;
+use noughts_and_crosses_core::{game::GameBoard, numerical::NumericalBoard, Tile};
+
+use crate::{
+    board::{self, LabelledTiles},
+    game_state::GameState,
+};
+
+pub struct Plugin;
+
+const NUMBER_SIZE: f32 = 32.0;
+// the same colours as the pieces
+const CROSS_COLOUR: Color = Color::RED;
+const NOUGHT_COLOUR: Color = Color::GREEN;
+const SELECTED: Color = Color::AZURE;
+const UNSELECTED: Color = Color::DARK_GRAY;
+
+impl bevy::app::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, (pick, update_tray, label_numbers).chain());
+    }
+}
+
+/// the number played in a cell of a numerical game, written in the corner of the tile
+#[derive(Component)]
+struct NumberLabel {
+    x: usize,
+    y: usize,
+}
+
+/// a button in the tray that picks one of your numbers to play
+#[derive(Component)]
+struct NumberButton(u8);
+
+/// the numbers the other seats have left
+#[derive(Component)]
+struct OthersText;
+
+#[derive(Component)]
+struct Tray;
+
+const fn colour(tile: Tile) -> Color {
+    match tile {
+        Tile::Cross => CROSS_COLOUR,
+        _ => NOUGHT_COLOUR,
+    }
+}
+
+fn setup(mut commands: Commands) {
+    for x in 0..3 {
+        for y in 0..3 {
+            commands.spawn((
+                TextBundle::default().with_style(Style {
+                    position_type: PositionType::Absolute,
+                    display: Display::None,
+                    ..default()
+                }),
+                NumberLabel { x, y },
+            ));
+        }
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(5.0),
+                    display: Display::None,
+                    ..default()
+                },
+                ..default()
+            },
+            Tray,
+        ))
+        .with_children(|tray| {
+            tray.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                ),
+                OthersText,
+            ));
+            tray.spawn(NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(5.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|row| {
+                for number in NumericalBoard::NUMBERS {
+                    row.spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(48.0),
+                                height: Val::Px(48.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: UNSELECTED.into(),
+                            ..default()
+                        },
+                        NumberButton(number),
+                    ))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            number.to_string(),
+                            TextStyle {
+                                font_size: 32.0,
+                                ..default()
+                            },
+                        ));
+                    });
+                }
+            });
+        });
+}
+
+/// picks a number from a button press, or the number keys
+fn pick(
+    keys: Res<Input<KeyCode>>,
+    buttons: Query<(&Interaction, &NumberButton), Changed<Interaction>>,
+    mut game_state: ResMut<GameState>,
+) {
+    if !matches!(game_state.game.board, GameBoard::Numerical(_)) {
+        return;
+    }
+
+    for (interaction, button) in &buttons {
+        if *interaction == Interaction::Pressed {
+            game_state.picked_number = Some(button.0);
+        }
+    }
+    let keys_pressed = [
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+        KeyCode::Key9,
+    ]
+    .into_iter()
+    .zip(NumericalBoard::NUMBERS)
+    .filter(|&(key, _)| keys.just_pressed(key));
+    for (_, number) in keys_pressed {
+        // only your own numbers can be picked
+        if game_state.numbers().contains(&number) {
+            game_state.picked_number = Some(number);
+        }
+    }
+}
+
+/// what the other seats have left to play. Spectators see both players' numbers.
+fn others_text(board: &NumericalBoard, game_state: &GameState) -> String {
+    let list = |tile| {
+        board
+            .remaining(tile)
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    if game_state.spectating {
+        format!(
+            "Crosses have {}. Noughts have {}.",
+            list(Tile::Cross),
+            list(Tile::Nought)
+        )
+    } else {
+        format!(
+            "Your opponent has {}.",
+            list(game_state.player().opponent())
+        )
+    }
+}
+
+/// shows the tray in numerical games, with your numbers left to play and the picked one
+/// highlighted
+fn update_tray(
+    game_state: Res<GameState>,
+    mut tray: Query<&mut Style, (With<Tray>, Without<NumberButton>)>,
+    mut others: Query<&mut Text, With<OthersText>>,
+    mut buttons: Query<(&mut BackgroundColor, &mut Style, &NumberButton)>,
+) {
+    let numerical = match &game_state.game.board {
+        GameBoard::Numerical(board) => Some(board),
+        _ => None,
+    };
+    for mut style in &mut tray {
+        style.display = if numerical.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    let Some(board) = numerical else {
+        return;
+    };
+
+    for mut text in &mut others {
+        text.sections[0].value = others_text(board, &game_state);
+    }
+
+    let numbers = if game_state.spectating {
+        Vec::new()
+    } else {
+        game_state.numbers()
+    };
+    let picked = game_state.number();
+    for (mut background, mut style, button) in &mut buttons {
+        style.display = if numbers.contains(&button.0) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        *background = if picked == Some(button.0) {
+            SELECTED
+        } else {
+            UNSELECTED
+        }
+        .into();
+    }
+}
+
+/// writes the numbers over each tile of a numerical game, following the tiles as the camera moves
+fn label_numbers(
+    game_state: Res<GameState>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    tile_query: LabelledTiles,
+    mut label_query: Query<(&NumberLabel, &mut Text, &mut Style)>,
+) {
+    let GameBoard::Numerical(numerical) = &game_state.game.board else {
+        for (_, _, mut style) in &mut label_query {
+            style.display = Display::None;
+        }
+        return;
+    };
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+
+    for (label, mut text, mut style) in &mut label_query {
+        let number = (label.x, label.y)
+            .try_into()
+            .ok()
+            .and_then(|cell| numerical.number(cell));
+        let corner = board::tile_corner(camera, &tile_query, label.x, label.y);
+        let (Some(number), Some(corner)) = (number, corner) else {
+            style.display = Display::None;
+            continue;
+        };
+
+        style.display = Display::Flex;
+        style.left = Val::Px(corner.x);
+        style.top = Val::Px(corner.y);
+        if game_state.is_changed() {
+            text.sections = vec![TextSection::new(
+                number.to_string(),
+                TextStyle {
+                    font_size: NUMBER_SIZE,
+                    color: colour(numerical.owner(number)),
+                    ..default()
+                },
+            )];
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use noughts_and_crosses_core::{game::GameBoard, quantum::QuantumBoard, Tile};
+
+use crate::{
+    board::{self, LabelledTiles},
+    game_state::GameState,
+};
+
+pub struct Plugin;
+
+const MARK_SIZE: f32 = 24.0;
+const SUBSCRIPT_SIZE: f32 = 14.0;
+// the same colours as the pieces
+const CROSS_COLOUR: Color = Color::RED;
+const NOUGHT_COLOUR: Color = Color::GREEN;
+
+impl bevy::app::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, label_marks);
+    }
+}
+
+/// the marks in a cell of a quantum game, written with their subscripts in the corner of the tile
+#[derive(Component)]
+struct MarkLabel {
+    x: usize,
+    y: usize,
+}
+
+fn setup(mut commands: Commands) {
+    for x in 0..3 {
+        for y in 0..3 {
+            commands.spawn((
+                TextBundle::default().with_style(Style {
+                    position_type: PositionType::Absolute,
+                    display: Display::None,
+                    ..default()
+                }),
+                MarkLabel { x, y },
+            ));
+        }
+    }
+}
+
+/// a mark followed by its subscript, written smaller
+fn mark_sections((tile, subscript): (Tile, usize)) -> [TextSection; 2] {
+    let color = if tile.is_cross() {
+        CROSS_COLOUR
+    } else {
+        NOUGHT_COLOUR
+    };
+    [
+        TextSection::new(
+            tile.to_string(),
+            TextStyle {
+                font_size: MARK_SIZE,
+                color,
+                ..default()
+            },
+        ),
+        TextSection::new(
+            format!("{subscript} "),
+            TextStyle {
+                font_size: SUBSCRIPT_SIZE,
+                color,
+                ..default()
+            },
+        ),
+    ]
+}
+
+/// every mark in the cell: the classical one first, then any spooky ones
+fn cell_sections(board: &QuantumBoard, x: usize, y: usize) -> Vec<TextSection> {
+    let Ok(cell) = (x, y).try_into() else {
+        return Vec::new();
+    };
+    board
+        .classical(cell)
+        .into_iter()
+        .chain(board.spooky(cell))
+        .flat_map(mark_sections)
+        .collect()
+}
+
+/// writes the marks over each tile of a quantum game, following the tiles as the camera moves
+fn label_marks(
+    game_state: Res<GameState>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    tile_query: LabelledTiles,
+    mut label_query: Query<(&MarkLabel, &mut Text, &mut Style)>,
+) {
+    let GameBoard::Quantum(quantum) = &game_state.game.board else {
+        for (_, _, mut style) in &mut label_query {
+            style.display = Display::None;
+        }
+        return;
+    };
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+
+    for (label, mut text, mut style) in &mut label_query {
+        let Some(corner) = board::tile_corner(camera, &tile_query, label.x, label.y) else {
+            style.display = Display::None;
+            continue;
+        };
+
+        style.display = Display::Flex;
+        style.left = Val::Px(corner.x);
+        style.top = Val::Px(corner.y);
+        if game_state.is_changed() {
+            text.sections = cell_sections(quantum, label.x, label.y);
+        }
+    }
+}
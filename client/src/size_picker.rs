@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+use noughts_and_crosses_core::{
+    game::GameBoard,
+    gobblet::{GobbletBoard, Size},
+};
+
+use crate::game_state::{GameState, GobbletPick};
+
+pub struct Plugin;
+
+const SELECTED: Color = Color::AZURE;
+const UNSELECTED: Color = Color::DARK_GRAY;
+
+impl bevy::app::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, (pick, update).chain());
+    }
+}
+
+/// a button that picks the size of piece to play in gobblet games, or to move one on the board
+#[derive(Component)]
+struct PickButton(GobbletPick);
+
+#[derive(Component)]
+struct Picker;
+
+const PICKS: [GobbletPick; 4] = [
+    GobbletPick::New(Size::Small),
+    GobbletPick::New(Size::Medium),
+    GobbletPick::New(Size::Large),
+    GobbletPick::Move,
+];
+
+/// the button's label, with how many pieces of its size are left
+fn label(pick: GobbletPick, pieces: usize) -> String {
+    match pick {
+        GobbletPick::New(Size::Small) => format!("S {pieces}"),
+        GobbletPick::New(Size::Medium) => format!("M {pieces}"),
+        GobbletPick::New(Size::Large) => format!("L {pieces}"),
+        GobbletPick::Move => "Move".to_string(),
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    column_gap: Val::Px(5.0),
+                    display: Display::None,
+                    ..default()
+                },
+                ..default()
+            },
+            Picker,
+        ))
+        .with_children(|parent| {
+            for pick in PICKS {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                min_width: Val::Px(48.0),
+                                height: Val::Px(48.0),
+                                padding: UiRect::horizontal(Val::Px(5.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: UNSELECTED.into(),
+                            ..default()
+                        },
+                        PickButton(pick),
+                    ))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            label(pick, GobbletBoard::PIECES),
+                            TextStyle {
+                                font_size: 24.0,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+/// picks from a button press, or the 1, 2, 3 and M keys
+fn pick(
+    keys: Res<Input<KeyCode>>,
+    buttons: Query<(&Interaction, &PickButton), Changed<Interaction>>,
+    mut game_state: ResMut<GameState>,
+) {
+    if !matches!(game_state.game.board, GameBoard::Gobblet(_)) {
+        return;
+    }
+
+    let mut picked = buttons
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| button.0);
+    for (key, pick) in [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::M]
+        .into_iter()
+        .zip(PICKS)
+    {
+        if keys.just_pressed(key) {
+            picked = Some(pick);
+        }
+    }
+    if let Some(pick) = picked {
+        game_state.gobblet_pick = pick;
+        // a piece already picked up is put back down
+        game_state.gobblet_from = None;
+    }
+}
+
+/// shows the picker to players of gobblet games, with the pick highlighted and the pieces left of
+/// each size
+fn update(
+    game_state: Res<GameState>,
+    mut picker: Query<&mut Style, With<Picker>>,
+    mut buttons: Query<(&mut BackgroundColor, &PickButton, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    let gobblet = match &game_state.game.board {
+        GameBoard::Gobblet(board) if !game_state.spectating => Some(board),
+        _ => None,
+    };
+    for mut style in &mut picker {
+        style.display = if gobblet.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    let Some(board) = gobblet else {
+        return;
+    };
+
+    for (mut background, button, children) in &mut buttons {
+        *background = if button.0 == game_state.gobblet_pick {
+            SELECTED
+        } else {
+            UNSELECTED
+        }
+        .into();
+
+        let pieces = match button.0 {
+            GobbletPick::New(size) => board.reserve(game_state.player(), size),
+            GobbletPick::Move => 0,
+        };
+        for &child in children {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.sections[0].value = label(button.0, pieces);
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use noughts_and_crosses_core::Tile;
+
+use crate::game_state::GameState;
+
+pub struct Plugin;
+
+const SELECTED: Color = Color::AZURE;
+const UNSELECTED: Color = Color::DARK_GRAY;
+
+impl bevy::app::Plugin for Plugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, (pick, update).chain());
+    }
+}
+
+/// a button that picks the symbol to place in wild and order and chaos games
+#[derive(Component)]
+struct SymbolButton(Tile);
+
+#[derive(Component)]
+struct Picker;
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    column_gap: Val::Px(5.0),
+                    display: Display::None,
+                    ..default()
+                },
+                ..default()
+            },
+            Picker,
+        ))
+        .with_children(|parent| {
+            for tile in [Tile::Cross, Tile::Nought] {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(48.0),
+                                height: Val::Px(48.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: UNSELECTED.into(),
+                            ..default()
+                        },
+                        SymbolButton(tile),
+                    ))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            tile.to_string(),
+                            TextStyle {
+                                font_size: 32.0,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+/// picks the symbol from a button press, or the X and O keys
+fn pick(
+    keys: Res<Input<KeyCode>>,
+    buttons: Query<(&Interaction, &SymbolButton), Changed<Interaction>>,
+    mut game_state: ResMut<GameState>,
+) {
+    if !game_state.picks_symbol() {
+        return;
+    }
+
+    for (interaction, button) in &buttons {
+        if *interaction == Interaction::Pressed {
+            game_state.wild_symbol = Some(button.0);
+        }
+    }
+    if keys.just_pressed(KeyCode::X) {
+        game_state.wild_symbol = Some(Tile::Cross);
+    }
+    if keys.just_pressed(KeyCode::O) {
+        game_state.wild_symbol = Some(Tile::Nought);
+    }
+}
+
+/// shows the picker to players who can place either symbol, with the picked symbol highlighted
+fn update(
+    game_state: Res<GameState>,
+    mut picker: Query<&mut Style, With<Picker>>,
+    mut buttons: Query<(&mut BackgroundColor, &SymbolButton)>,
+) {
+    let visible = game_state.picks_symbol() && !game_state.spectating;
+    for mut style in &mut picker {
+        style.display = if visible {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+
+    let symbol = game_state.symbol();
+    for (mut background, button) in &mut buttons {
+        *background = if button.0 == symbol {
+            SELECTED
+        } else {
+            UNSELECTED
+        }
+        .into();
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use noughts_and_crosses_core::{
+    bitboard::{BitBoard, Lines},
+    solver::{Solver, Value},
+    Board, GameSummary, Tile,
+};
+
+/// the solver as it was before bitboards, for comparison
+fn board_value(cache: &mut HashMap<(Board, Tile), Value>, board: &Board, to_move: Tile) -> Value {
+    let key = (board.clone(), to_move);
+    if let Some(value) = cache.get(&key) {
+        return *value;
+    }
+
+    let value = match board.summary() {
+        GameSummary::InProgress => {
+            let mut best = Value::Loss;
+            for pos in board.legal_moves() {
+                let mut next = board.clone();
+                next.play(pos, to_move).unwrap();
+                best = best.max(board_value(cache, &next, to_move.opponent()).flip());
+                if best == Value::Win {
+                    break;
+                }
+            }
+            best
+        }
+        GameSummary::Tie => Value::Draw,
+        summary if summary.winner() == Some(to_move) => Value::Win,
+        _ => Value::Loss,
+    };
+
+    cache.insert(key, value);
+    value
+}
+
+fn summary(c: &mut Criterion) {
+    let board: Board = "XO./.XO/..X".parse().unwrap();
+    let lines = Lines::new(board.size()).unwrap();
+    let bits = BitBoard::try_from(&board).unwrap();
+
+    let mut group = c.benchmark_group("summary");
+    group.bench_function("board", |b| b.iter(|| black_box(&board).summary()));
+    group.bench_function("bitboard", |b| {
+        b.iter(|| black_box(&bits).summary(&lines));
+    });
+    group.finish();
+}
+
+fn solve(c: &mut Criterion) {
+    let board = Board::new();
+
+    let mut group = c.benchmark_group("solve empty board");
+    group.bench_function("board", |b| {
+        b.iter(|| board_value(&mut HashMap::new(), black_box(&board), Tile::Cross));
+    });
+    group.bench_function("bitboard", |b| {
+        b.iter(|| Solver::new().value(black_box(&board), Tile::Cross));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, summary, solve);
+criterion_main!(benches);
@@ -0,0 +1,267 @@
+use thiserror::Error;
+
+use crate::{game_state::Position, Board, BoardSize, GameSummary, MoveError, Tile};
+
+#[derive(Error, Debug)]
+pub enum BitBoardError {
+    #[error("only boards of up to 64 tiles can be packed")]
+    TooLarge,
+}
+
+/// every line of a board size that wins the game, as a mask of tiles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lines {
+    size: BoardSize,
+    masks: Vec<u64>,
+    full: u64,
+}
+
+impl Lines {
+    /// works out the lines for the board size. Do this once per size, rather than once per move.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the board has more than 64 tiles.
+    pub fn new(size: BoardSize) -> Result<Self, BitBoardError> {
+        let tiles = size.width() * size.height();
+        if tiles > 64 {
+            return Err(BitBoardError::TooLarge);
+        }
+
+        let board = Board::with_size(size);
+        let masks = board
+            .positions()
+            .flat_map(|start| {
+                Board::DIRECTIONS.into_iter().filter_map(move |(dx, dy)| {
+                    let mut mask = 0;
+                    for i in 0..size.win_length() {
+                        let x = start.x().checked_add_signed(dx * i.cast_signed())?;
+                        let y = start.y().checked_add_signed(dy * i.cast_signed())?;
+                        if x >= size.width() || y >= size.height() {
+                            return None;
+                        }
+                        mask |= 1 << BitBoard::index(x, y, size);
+                    }
+                    Some(mask)
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            size,
+            masks,
+            full: u64::MAX >> (64 - tiles),
+        })
+    }
+
+    pub const fn size(&self) -> BoardSize {
+        self.size
+    }
+
+    /// a mask with every tile of the board set
+    pub const fn full(&self) -> u64 {
+        self.full
+    }
+
+    pub fn masks(&self) -> &[u64] {
+        &self.masks
+    }
+}
+
+/// a board packed into a bitmask per side, for searching quickly. Tile `(x, y)` is bit
+/// `x * height + y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitBoard {
+    pub crosses: u64,
+    pub noughts: u64,
+    size: BoardSize,
+}
+
+impl TryFrom<&Board> for BitBoard {
+    type Error = BitBoardError;
+
+    fn try_from(board: &Board) -> Result<Self, Self::Error> {
+        let size = board.size();
+        if size.width() * size.height() > 64 {
+            return Err(BitBoardError::TooLarge);
+        }
+
+        let mut bits = Self {
+            crosses: 0,
+            noughts: 0,
+            size,
+        };
+        for pos in board.positions() {
+            let bit = 1 << Self::index(pos.x(), pos.y(), size);
+            match board.tiles[pos.x()][pos.y()] {
+                Tile::Cross => bits.crosses |= bit,
+                Tile::Nought => bits.noughts |= bit,
+                Tile::Unplayed => {}
+            }
+        }
+        Ok(bits)
+    }
+}
+
+impl From<BitBoard> for Board {
+    fn from(bits: BitBoard) -> Self {
+        let mut board = Self::with_size(bits.size);
+        for pos in board.clone().positions() {
+            board.tiles[pos.x()][pos.y()] = bits.get(pos).unwrap_or_default();
+        }
+        board
+    }
+}
+
+impl BitBoard {
+    const fn index(x: usize, y: usize, size: BoardSize) -> usize {
+        x * size.height() + y
+    }
+
+    /// the bit for the position, if it is on the board
+    const fn bit(&self, pos: Position) -> Option<u64> {
+        if pos.x() < self.size.width() && pos.y() < self.size.height() {
+            Some(1 << Self::index(pos.x(), pos.y(), self.size))
+        } else {
+            None
+        }
+    }
+
+    /// the position of a bit index
+    const fn position(index: usize, size: BoardSize) -> Position {
+        // boards are at most 64 tiles, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        Position::new((index / size.height()) as u8, (index % size.height()) as u8)
+    }
+
+    pub const fn size(&self) -> BoardSize {
+        self.size
+    }
+
+    /// the tile at the position, if it is on the board
+    pub const fn get(&self, pos: Position) -> Option<Tile> {
+        let Some(bit) = self.bit(pos) else {
+            return None;
+        };
+        Some(if self.crosses & bit != 0 {
+            Tile::Cross
+        } else if self.noughts & bit != 0 {
+            Tile::Nought
+        } else {
+            Tile::Unplayed
+        })
+    }
+
+    /// checks each line once, with a couple of bit operations each
+    pub fn summary(&self, lines: &Lines) -> GameSummary {
+        debug_assert_eq!(
+            lines.size, self.size,
+            "lines are for a different board size"
+        );
+
+        for &line in &lines.masks {
+            if self.crosses & line == line {
+                return GameSummary::CrossWin;
+            }
+            if self.noughts & line == line {
+                return GameSummary::NoughtWin;
+            }
+        }
+
+        if (self.crosses | self.noughts) & lines.full == lines.full {
+            GameSummary::Tie
+        } else {
+            GameSummary::InProgress
+        }
+    }
+
+    /// every unplayed position. Doesn't check whether the game is over.
+    pub fn empty(&self, lines: &Lines) -> impl Iterator<Item = Position> {
+        let size = self.size;
+        let mut empty = !(self.crosses | self.noughts) & lines.full;
+        core::iter::from_fn(move || {
+            if empty == 0 {
+                return None;
+            }
+            let index = empty.trailing_zeros() as usize;
+            empty &= empty - 1;
+            Some(Self::position(index, size))
+        })
+    }
+
+    /// plays the tile without checking whether the game is over
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position is off the board or already played.
+    pub fn play(&mut self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        let bit = self.bit(pos).ok_or(MoveError::OutOfBounds)?;
+        if (self.crosses | self.noughts) & bit != 0 {
+            return Err(MoveError::Occupied);
+        }
+        match tile {
+            Tile::Cross => self.crosses |= bit,
+            Tile::Nought => self.noughts |= bit,
+            Tile::Unplayed => return Err(MoveError::Unplayed),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classic_lines() {
+        let lines = Lines::new(BoardSize::default()).unwrap();
+        assert_eq!(lines.masks().len(), 8);
+        assert_eq!(lines.full(), 0b1_1111_1111);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let board: Board = "X.O/.X./O.X".parse().unwrap();
+        let bits = BitBoard::try_from(&board).unwrap();
+        assert_eq!(bits.get((2, 0).try_into().unwrap()), Some(Tile::Nought));
+        assert_eq!(Board::from(bits), board);
+    }
+
+    #[test]
+    fn test_summary_matches_board() {
+        let size = BoardSize::new(5, 4, 4).unwrap();
+        let lines = Lines::new(size).unwrap();
+        for notation in [
+            "...../...../...../.....:4",
+            "X..../.X.../..X../...X.:4",
+            "..O../..O../..O../..O..:4",
+            "XOXOX/XOXOX/OXOXO/OXOXO:4",
+            "...OX/..OX./.OX../OX...:4",
+        ] {
+            let board: Board = notation.parse().unwrap();
+            assert_eq!(
+                BitBoard::try_from(&board).unwrap().summary(&lines),
+                board.summary(),
+                "{notation}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_too_large() {
+        let board = Board::with_size(BoardSize::new(9, 9, 3).unwrap());
+        assert!(BitBoard::try_from(&board).is_err());
+        assert!(Lines::new(board.size()).is_err());
+    }
+
+    #[test]
+    fn test_play_and_empty() {
+        let lines = Lines::new(BoardSize::default()).unwrap();
+        let mut bits = BitBoard::try_from(&Board::new()).unwrap();
+        let centre = (1, 1).try_into().unwrap();
+        bits.play(centre, Tile::Cross).unwrap();
+        assert_eq!(bits.play(centre, Tile::Nought), Err(MoveError::Occupied));
+        assert_eq!(bits.empty(&lines).count(), 8);
+        assert!(!bits.empty(&lines).any(|pos| pos == centre));
+    }
+}
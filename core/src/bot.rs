@@ -0,0 +1,560 @@
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    game::{Game, GameBoard},
+    game_state::Position,
+    gobblet::GobbletMove,
+    quantum::QuantumMove,
+    solver::Solver,
+    Board, MoveError, Tile,
+};
+
+#[derive(Error, Debug)]
+pub enum DifficultyParseError {
+    #[error("must be one of random, easy, hard or perfect")]
+    Unknown,
+}
+
+/// how well the computer opponent plays
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Difficulty {
+    /// any legal move
+    #[default]
+    Random,
+    /// takes a win or blocks a loss when there is one, otherwise plays randomly
+    Easy,
+    /// plays perfectly most of the time
+    Hard,
+    /// never loses
+    Perfect,
+}
+
+impl TryFrom<&str> for Difficulty {
+    type Error = DifficultyParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "random" => Ok(Self::Random),
+            "easy" => Ok(Self::Easy),
+            "hard" => Ok(Self::Hard),
+            "perfect" => Ok(Self::Perfect),
+            _ => Err(Self::Error::Unknown),
+        }
+    }
+}
+
+impl Difficulty {
+    // how often the hard computer plays perfectly
+    const HARD_ACCURACY: f64 = 0.75;
+    // the most unplayed tiles the solver is used for. Larger boards can't be searched exhaustively
+    // until they fill up.
+    const SEARCH_LIMIT: usize = 10;
+    // the most moves checked for handing the opponent a win in wild games, where every move is
+    // looked at for each reply
+    const LOOK_AHEAD_LIMIT: usize = 64;
+    // the most moves considered anywhere on the board. With more, only moves next to a played
+    // tile are considered.
+    const NEARBY_LIMIT: usize = 25;
+
+    /// picks a move for `to_move` and the symbol to place, or none if the game is over
+    pub fn choose_move(self, game: &Game, to_move: Tile) -> Option<(Position, Tile)> {
+        let mut r = rand::thread_rng();
+
+        let mut moves = game.placements(to_move);
+
+        if self != Self::Random {
+            // taking a win is always the best move, so the solver isn't needed
+            if let Some(placement) = winning_move(game, to_move) {
+                return Some(placement);
+            }
+
+            // neither is blocking a loss. In wild games a threat is a win for either side, so
+            // there's never anything to block. In order and chaos, chaos blocks with whichever
+            // symbol doesn't complete the line.
+            if let Some((pos, _)) = winning_move(game, to_move.opponent()) {
+                if let Some(&placement) = moves.iter().find(|&&(p, symbol)| {
+                    let mut next = game.clone();
+                    p == pos
+                        && next.place(p, symbol, to_move).is_ok()
+                        && next.summary().winner() != Some(to_move.opponent())
+                }) {
+                    return Some(placement);
+                }
+            }
+
+            // avoid moves that hand the game to the opponent, such as completing a line in misère
+            // or leaving two in a row when the opponent can complete a line of either symbol
+            let look_ahead =
+                game.symbols(to_move.opponent()).len() > 1 && moves.len() <= Self::LOOK_AHEAD_LIMIT;
+            let safe: Vec<(Position, Tile)> = moves
+                .iter()
+                .copied()
+                .filter(|&(pos, symbol)| {
+                    let mut next = game.clone();
+                    next.place(pos, symbol, to_move).is_ok()
+                        && next.summary().winner() != Some(to_move.opponent())
+                        && !(look_ahead && winning_move(&next, to_move.opponent()).is_some())
+                })
+                .collect();
+            if !safe.is_empty() {
+                moves = safe;
+            }
+
+            // on large boards, play next to the pieces already played
+            if moves.len() > Self::NEARBY_LIMIT {
+                let tiles = game.tiles();
+                let nearby: Vec<(Position, Tile)> = moves
+                    .iter()
+                    .copied()
+                    .filter(|&(pos, _)| is_nearby(&tiles, pos))
+                    .collect();
+                if !nearby.is_empty() {
+                    moves = nearby;
+                }
+            }
+        }
+
+        let perfect = match self {
+            Self::Random | Self::Easy => false,
+            Self::Hard => r.gen_bool(Self::HARD_ACCURACY),
+            Self::Perfect => true,
+        };
+
+        // only standard games that fit in a bitboard can be solved
+        if let GameBoard::Standard(board) = &game.board {
+            if perfect && game.legal_moves().len() <= Self::SEARCH_LIMIT {
+                if let Ok(solution) = Solver::with_rules(game.rules).solve(board, to_move) {
+                    // when every move loses, hold out rather than losing straight away
+                    let safe: Vec<(Position, Tile)> = solution
+                        .moves
+                        .iter()
+                        .copied()
+                        .filter(|placement| moves.contains(placement))
+                        .collect();
+                    return safe
+                        .choose(&mut r)
+                        .or_else(|| solution.moves.choose(&mut r))
+                        .copied();
+                }
+            }
+        }
+
+        moves.choose(&mut r).copied()
+    }
+}
+
+impl Difficulty {
+    /// picks a quantum move for `to_move`, or none if the game is over. Other than at random, the
+    /// computer takes a win when it can and avoids handing one over, assuming the opponent
+    /// collapses any cycle it makes in the way that suits them.
+    pub fn choose_quantum_move(self, game: &Game, to_move: Tile) -> Option<QuantumMove> {
+        let mut r = rand::thread_rng();
+        let moves = game.quantum_moves();
+        if self == Self::Random {
+            return moves.choose(&mut r).copied();
+        }
+
+        let value = |game: &Game| match game.summary().winner() {
+            Some(winner) if winner == to_move => 1,
+            Some(_) => -1,
+            None => 0,
+        };
+        let scores: Vec<i8> = moves
+            .iter()
+            .map(|&m| {
+                let mut next = game.clone();
+                if next.play_quantum(m, to_move).is_err() {
+                    return i8::MIN;
+                }
+                let replies = next.pending_collapse().map_or_else(Vec::new, |cells| {
+                    cells
+                        .into_iter()
+                        .filter_map(|cell| {
+                            let mut collapsed = next.clone();
+                            collapsed
+                                .play_quantum(QuantumMove::Collapse(cell), to_move.opponent())
+                                .ok()
+                                .map(|()| value(&collapsed))
+                        })
+                        .collect()
+                });
+                replies.into_iter().min().unwrap_or_else(|| value(&next))
+            })
+            .collect();
+
+        let best = scores.iter().copied().max()?;
+        let best_moves: Vec<QuantumMove> = moves
+            .into_iter()
+            .zip(scores)
+            .filter_map(|(m, score)| (score == best).then_some(m))
+            .collect();
+        best_moves.choose(&mut r).copied()
+    }
+
+    /// picks a gobblet move for `to_move`, or none if the game is over. Other than at random, the
+    /// computer takes a win when it can and avoids moves that leave the opponent one, including
+    /// lifting a piece off the opponent's line.
+    pub fn choose_gobblet_move(self, game: &Game, to_move: Tile) -> Option<GobbletMove> {
+        let moves = game.gobblet_moves(to_move);
+        if self == Self::Random {
+            return moves.choose(&mut rand::thread_rng()).copied();
+        }
+        look_ahead(
+            game,
+            to_move,
+            moves,
+            Game::gobblet_moves,
+            Game::play_gobblet,
+        )
+    }
+
+    /// picks a cell and number for `to_move` in a numerical game, or none if the game is over.
+    /// Other than at random, the computer completes a line adding up to 15 when it can and avoids
+    /// leaving the opponent one.
+    pub fn choose_number_move(self, game: &Game, to_move: Tile) -> Option<(Position, u8)> {
+        let moves = game.number_moves(to_move);
+        if self == Self::Random {
+            return moves.choose(&mut rand::thread_rng()).copied();
+        }
+        look_ahead(
+            game,
+            to_move,
+            moves,
+            Game::number_moves,
+            |game, (pos, number), player| game.play_number(pos, number, player),
+        )
+    }
+}
+
+/// picks at random from the moves that win for `to_move`, or failing that those that don't leave
+/// the opponent a winning reply
+fn look_ahead<M: Copy>(
+    game: &Game,
+    to_move: Tile,
+    moves: Vec<M>,
+    moves_for: impl Fn(&Game, Tile) -> Vec<M>,
+    play: impl Fn(&mut Game, M, Tile) -> Result<(), MoveError>,
+) -> Option<M> {
+    let after = |game: &Game, m: M, player: Tile| {
+        let mut next = game.clone();
+        play(&mut next, m, player).ok().map(|()| next)
+    };
+    let scores: Vec<i8> = moves
+        .iter()
+        .map(|&m| {
+            let Some(next) = after(game, m, to_move) else {
+                return i8::MIN;
+            };
+            match next.summary().winner() {
+                Some(winner) if winner == to_move => 1,
+                Some(_) => -1,
+                None if moves_for(&next, to_move.opponent())
+                    .into_iter()
+                    .filter_map(|reply| after(&next, reply, to_move.opponent()))
+                    .any(|reply| reply.summary().winner() == Some(to_move.opponent())) =>
+                {
+                    -1
+                }
+                None => 0,
+            }
+        })
+        .collect();
+
+    let best = scores.iter().copied().max()?;
+    let best_moves: Vec<M> = moves
+        .into_iter()
+        .zip(scores)
+        .filter_map(|(m, score)| (score == best).then_some(m))
+        .collect();
+    best_moves.choose(&mut rand::thread_rng()).copied()
+}
+
+/// whether any tile around the position has been played
+fn is_nearby(tiles: &Board, pos: Position) -> bool {
+    (-1..=1).any(|dx: isize| {
+        (-1..=1).any(|dy: isize| {
+            let x = pos.x().checked_add_signed(dx);
+            let y = pos.y().checked_add_signed(dy);
+            x.zip(y)
+                .and_then(|xy| Position::try_from(xy).ok())
+                .and_then(|near| tiles.get(near))
+                .is_some_and(|tile| !tile.is_unplayed())
+        })
+    })
+}
+
+/// a move that wins the game for `player`, if there is one
+fn winning_move(game: &Game, player: Tile) -> Option<(Position, Tile)> {
+    game.placements(player).into_iter().find(|&(pos, symbol)| {
+        let mut next = game.clone();
+        next.place(pos, symbol, player).is_ok() && next.summary().winner() == Some(player)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gobblet::Size,
+        gomoku::{GomokuBoard, Restriction},
+        notakto::NotaktoBoard,
+        qubic::QubicBoard,
+        testing::pos,
+        ultimate::UltimateBoard,
+        Rules,
+    };
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Difficulty::try_from("hard").unwrap(), Difficulty::Hard);
+        assert!(Difficulty::try_from("impossible").is_err());
+    }
+
+    #[test]
+    fn test_random_plays_legal_move() {
+        let board = Board::from([
+            [Tile::Cross, Tile::Nought, Tile::Cross],
+            [Tile::Nought, Tile::Unplayed, Tile::Cross],
+            [Tile::Nought, Tile::Cross, Tile::Nought],
+        ]);
+        assert_eq!(
+            Difficulty::Random.choose_move(&Game::from(board), Tile::Cross),
+            Some(((1, 1).try_into().unwrap(), Tile::Cross))
+        );
+    }
+
+    #[test]
+    fn test_full_board_has_no_move() {
+        let board = Board::from([
+            [Tile::Nought, Tile::Cross, Tile::Nought],
+            [Tile::Nought, Tile::Cross, Tile::Nought],
+            [Tile::Cross, Tile::Nought, Tile::Cross],
+        ]);
+        assert_eq!(
+            Difficulty::Perfect.choose_move(&Game::from(board), Tile::Cross),
+            None
+        );
+    }
+
+    #[test]
+    fn test_easy_blocks() {
+        let board = Board::from([
+            [Tile::Cross, Tile::Cross, Tile::Unplayed],
+            [Tile::Unplayed, Tile::Nought, Tile::Unplayed],
+            [Tile::Unplayed; 3],
+        ]);
+        assert_eq!(
+            Difficulty::Easy.choose_move(&Game::from(board), Tile::Nought),
+            Some(((0, 2).try_into().unwrap(), Tile::Nought))
+        );
+    }
+
+    #[test]
+    fn test_perfect_takes_the_win() {
+        let board = Board::from([
+            [Tile::Cross, Tile::Cross, Tile::Unplayed],
+            [Tile::Nought, Tile::Nought, Tile::Unplayed],
+            [Tile::Unplayed; 3],
+        ]);
+        assert_eq!(
+            Difficulty::Perfect.choose_move(&Game::from(board), Tile::Nought),
+            Some(((1, 2).try_into().unwrap(), Tile::Nought))
+        );
+    }
+
+    #[test]
+    fn test_ultimate_plays_in_active_board() {
+        let mut board = UltimateBoard::new();
+        board.play((4, 2).try_into().unwrap(), Tile::Cross).unwrap();
+        let (pos, _) = Difficulty::Perfect
+            .choose_move(&Game::from(board.clone()), Tile::Nought)
+            .unwrap();
+        assert!(board.is_legal(pos));
+    }
+
+    #[test]
+    fn test_misere_avoids_completing_a_line() {
+        let mut game = Game::from(Board::from([
+            [Tile::Cross, Tile::Cross, Tile::Unplayed],
+            [Tile::Nought, Tile::Unplayed, Tile::Nought],
+            [Tile::Nought, Tile::Cross, Tile::Unplayed],
+        ]));
+        game.rules = Rules::Misere;
+        for difficulty in [Difficulty::Easy, Difficulty::Perfect] {
+            let (pos, _) = difficulty.choose_move(&game, Tile::Cross).unwrap();
+            assert_ne!(pos, (0, 2).try_into().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_wild_completes_either_line() {
+        let mut game = Game::from(Board::from([
+            [Tile::Nought, Tile::Nought, Tile::Unplayed],
+            [Tile::Unplayed, Tile::Cross, Tile::Unplayed],
+            [Tile::Unplayed; 3],
+        ]));
+        game.rules = Rules::Wild;
+        assert_eq!(
+            Difficulty::Easy.choose_move(&game, Tile::Cross),
+            Some(((0, 2).try_into().unwrap(), Tile::Nought))
+        );
+    }
+
+    #[test]
+    fn test_wild_avoids_two_in_a_row() {
+        let mut game = Game::from(Board::from([
+            [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
+            [Tile::Unplayed; 3],
+            [Tile::Unplayed; 3],
+        ]));
+        game.rules = Rules::Wild;
+        let (pos, symbol) = Difficulty::Easy.choose_move(&game, Tile::Nought).unwrap();
+        game.place(pos, symbol, Tile::Nought).unwrap();
+        assert_eq!(winning_move(&game, Tile::Cross), None);
+    }
+
+    #[test]
+    fn test_notakto_avoids_killing_the_last_board() {
+        let mut board = NotaktoBoard::new(1);
+        board.boards[0] = "XX./.../...".parse().unwrap();
+        let game = Game::from(board);
+        for difficulty in [Difficulty::Easy, Difficulty::Perfect] {
+            let (pos, symbol) = difficulty.choose_move(&game, Tile::Nought).unwrap();
+            assert_eq!(symbol, Tile::Cross);
+            assert_ne!(pos, (2, 0).try_into().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_gomoku_plays_nearby() {
+        let mut board = GomokuBoard::default();
+        board.play((7, 7).try_into().unwrap(), Tile::Cross).unwrap();
+        let (pos, _) = Difficulty::Easy
+            .choose_move(&Game::from(board), Tile::Nought)
+            .unwrap();
+        assert!(pos.x().abs_diff(7) <= 1 && pos.y().abs_diff(7) <= 1);
+    }
+
+    #[test]
+    fn test_gomoku_first_player_avoids_forbidden_moves() {
+        let mut board = GomokuBoard::new(Restriction::Renju);
+        for (x, y) in [(0, 0), (1, 0), (2, 0), (4, 0), (5, 0)] {
+            board.board.tiles[x][y] = Tile::Cross;
+        }
+        board.first = Some(Tile::Cross);
+        let game = Game::from(board);
+        for _ in 0..10 {
+            let (pos, _) = Difficulty::Random.choose_move(&game, Tile::Cross).unwrap();
+            assert_ne!(pos, (3, 0).try_into().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_qubic_blocks_line_through_layers() {
+        let mut board = QubicBoard::default();
+        for (x, tile) in [
+            (0, Tile::Cross),
+            (1, Tile::Nought),
+            (4, Tile::Cross),
+            (5, Tile::Nought),
+        ] {
+            board.play((x, 0).try_into().unwrap(), tile).unwrap();
+        }
+        board.play((8, 0).try_into().unwrap(), Tile::Cross).unwrap();
+        assert_eq!(
+            Difficulty::Easy.choose_move(&Game::from(board), Tile::Nought),
+            Some(((12, 0).try_into().unwrap(), Tile::Nought))
+        );
+    }
+
+    #[test]
+    fn test_quantum_collapses_into_a_win() {
+        let mut game = Game::from(crate::quantum::QuantumBoard::default());
+        for (m, player) in [
+            (QuantumMove::Entangle(pos(0, 0), pos(2, 2)), Tile::Nought),
+            (QuantumMove::Entangle(pos(2, 2), pos(0, 0)), Tile::Cross),
+            (QuantumMove::Collapse(pos(2, 2)), Tile::Nought),
+            (QuantumMove::Entangle(pos(1, 0), pos(2, 1)), Tile::Nought),
+            (QuantumMove::Entangle(pos(1, 2), pos(2, 0)), Tile::Cross),
+            (QuantumMove::Entangle(pos(2, 0), pos(1, 0)), Tile::Nought),
+            (QuantumMove::Entangle(pos(2, 1), pos(1, 2)), Tile::Cross),
+        ] {
+            game.play_quantum(m, player).unwrap();
+        }
+        // noughts' mark 1 is in (0, 0). Collapsing crosses' last mark into (2, 1) puts noughts
+        // in (1, 0) and (2, 0), completing the top row.
+        assert_eq!(
+            Difficulty::Easy.choose_quantum_move(&game, Tile::Nought),
+            Some(QuantumMove::Collapse(pos(2, 1)))
+        );
+    }
+
+    #[test]
+    fn test_chaos_blocks_with_the_other_symbol() {
+        let mut game = Game::from(crate::order_chaos::OrderChaosBoard::default());
+        for (x, symbol) in [
+            (0, Tile::Cross),
+            (1, Tile::Cross),
+            (2, Tile::Cross),
+            (3, Tile::Cross),
+            (5, Tile::Nought),
+        ] {
+            game.place((x, 0).try_into().unwrap(), symbol, Tile::Cross)
+                .unwrap();
+        }
+        assert_eq!(
+            Difficulty::Easy.choose_move(&game, Tile::Nought),
+            Some(((4, 0).try_into().unwrap(), Tile::Nought))
+        );
+    }
+
+    #[test]
+    fn test_numerical_blocks_fifteen() {
+        let mut game = Game::from(crate::numerical::NumericalBoard::default());
+        for (pos, number, player) in [
+            (pos(0, 0), 9, Tile::Cross),
+            (pos(2, 1), 6, Tile::Nought),
+            (pos(1, 0), 1, Tile::Cross),
+        ] {
+            game.play_number(pos, number, player).unwrap();
+        }
+        // crosses complete the top row with a 5 at (2, 0). Noughts have to fill it, and only their
+        // 8 doesn't leave crosses an odd number to finish the right column.
+        assert_eq!(
+            Difficulty::Easy.choose_number_move(&game, Tile::Nought),
+            Some((pos(2, 0), 8))
+        );
+    }
+
+    #[test]
+    fn test_gobblet_blocks_a_line() {
+        let mut game = Game::from(crate::gobblet::GobbletBoard::default());
+        for (size, to, player) in [
+            (Size::Medium, pos(0, 0), Tile::Cross),
+            (Size::Small, pos(2, 1), Tile::Nought),
+            (Size::Medium, pos(1, 0), Tile::Cross),
+            (Size::Small, pos(0, 2), Tile::Nought),
+        ] {
+            let m = GobbletMove {
+                size,
+                from: None,
+                to,
+            };
+            game.play_gobblet(m, player).unwrap();
+        }
+        // a small or medium nought at (2, 0) would be covered, so noughts must play a large piece
+        // there or over one of the crosses
+        let m = Difficulty::Easy
+            .choose_gobblet_move(&game, Tile::Nought)
+            .unwrap();
+        let mut next = game.clone();
+        next.play_gobblet(m, Tile::Nought).unwrap();
+        assert!(next.gobblet_moves(Tile::Cross).into_iter().all(|reply| {
+            let mut after = next.clone();
+            after.play_gobblet(reply, Tile::Cross).unwrap();
+            after.summary().winner() != Some(Tile::Cross)
+        }));
+    }
+}
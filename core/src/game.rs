@@ -0,0 +1,733 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game_state::Position,
+    gobblet::{GobbletBoard, GobbletMove},
+    gomoku::GomokuBoard,
+    gravity::Column,
+    infinite::InfiniteBoard,
+    notakto::NotaktoBoard,
+    numerical::NumericalBoard,
+    options::{GameOptions, Mode},
+    order_chaos::{OrderChaosBoard, Role},
+    quantum::{QuantumBoard, QuantumMove},
+    qubic::QubicBoard,
+    record::Play,
+    ultimate::UltimateBoard,
+    validate::InvalidBoard,
+    Board, GameSummary, MoveError, Rules, Tile,
+};
+
+/// the board for each mode
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GameBoard {
+    Standard(Board),
+    Ultimate(Box<UltimateBoard>),
+    Notakto(NotaktoBoard),
+    Gomoku(Box<GomokuBoard>),
+    /// pieces fall to the bottom of their column
+    Gravity(Board),
+    /// a 4x4x4 cube
+    Qubic(QubicBoard),
+    /// marks are played in two cells at once until they collapse
+    Quantum(QuantumBoard),
+    /// each symbol's oldest piece is removed when a fourth is placed
+    Infinite(InfiniteBoard),
+    /// larger pieces can be placed over smaller ones
+    Gobblet(GobbletBoard),
+    /// one seat tries to make five in a row of either symbol and the other tries to stop them
+    OrderChaos(OrderChaosBoard),
+    /// the numbers 1 to 9 are played, and a line adding up to 15 wins
+    Numerical(NumericalBoard),
+}
+
+impl Default for GameBoard {
+    fn default() -> Self {
+        Self::Standard(Board::new())
+    }
+}
+
+/// the state of play for any mode
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Game {
+    #[serde(default)]
+    pub rules: Rules,
+    pub board: GameBoard,
+    /// the player who moved last. In wild games this decides who completed a line.
+    #[serde(default)]
+    pub last_mover: Option<Tile>,
+}
+
+impl From<GameBoard> for Game {
+    fn from(board: GameBoard) -> Self {
+        Self {
+            rules: Rules::default(),
+            board,
+            last_mover: None,
+        }
+    }
+}
+
+/// a game of each kind of board, with the default rules
+macro_rules! impl_from_board {
+    ($($board:ty => $variant:ident,)*) => {
+        $(
+            impl From<$board> for Game {
+                fn from(board: $board) -> Self {
+                    GameBoard::$variant(board.into()).into()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_board! {
+    Board => Standard,
+    UltimateBoard => Ultimate,
+    NotaktoBoard => Notakto,
+    GomokuBoard => Gomoku,
+    QubicBoard => Qubic,
+    QuantumBoard => Quantum,
+    InfiniteBoard => Infinite,
+    GobbletBoard => Gobblet,
+    OrderChaosBoard => OrderChaos,
+    NumericalBoard => Numerical,
+}
+
+impl Game {
+    /// an empty game set up with the options
+    pub fn new(options: &GameOptions) -> Self {
+        Self {
+            rules: options.rules,
+            board: match options.mode {
+                Mode::Standard => GameBoard::Standard(Board::with_size(options.size)),
+                Mode::Ultimate => GameBoard::Ultimate(Box::default()),
+                Mode::Notakto => GameBoard::Notakto(NotaktoBoard::default()),
+                Mode::Gomoku => GameBoard::Gomoku(Box::new(GomokuBoard::new(options.restriction))),
+                Mode::Gravity => GameBoard::Gravity(Board::with_size(options.size)),
+                Mode::Qubic => GameBoard::Qubic(QubicBoard::default()),
+                Mode::Quantum => GameBoard::Quantum(QuantumBoard::default()),
+                Mode::Infinite => GameBoard::Infinite(InfiniteBoard::default()),
+                Mode::Gobblet => GameBoard::Gobblet(GobbletBoard::default()),
+                Mode::OrderChaos => GameBoard::OrderChaos(OrderChaosBoard::default()),
+                Mode::Numerical => GameBoard::Numerical(NumericalBoard::default()),
+            },
+            last_mover: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        match &mut self.board {
+            GameBoard::Standard(board) | GameBoard::Gravity(board) => board.clear(),
+            GameBoard::Ultimate(board) => board.clear(),
+            GameBoard::Notakto(board) => board.clear(),
+            GameBoard::Gomoku(board) => board.clear(),
+            GameBoard::Qubic(board) => board.clear(),
+            GameBoard::Quantum(board) => board.clear(),
+            GameBoard::Infinite(board) => board.clear(),
+            GameBoard::Gobblet(board) => board.clear(),
+            GameBoard::OrderChaos(board) => board.clear(),
+            GameBoard::Numerical(board) => board.clear(),
+        }
+        self.last_mover = None;
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary, and the line that decided the game if there is one
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        let (summary, line) = match &self.board {
+            GameBoard::Standard(board) | GameBoard::Gravity(board) => board.summary_with_line(),
+            GameBoard::Infinite(board) => board.board.summary_with_line(),
+            GameBoard::Ultimate(board) => board.summary_with_line(),
+            GameBoard::Gomoku(board) => board.summary_with_line(),
+            GameBoard::Qubic(board) => board.summary_with_line(),
+            GameBoard::Quantum(board) => board.summary_with_line(),
+            GameBoard::Gobblet(board) => board.summary_with_line(),
+            GameBoard::Numerical(board) => board.summary_with_line(),
+            GameBoard::Notakto(board) => {
+                // whoever kills the last board loses, whatever the rules
+                let (summary, line) = board.summary_with_line();
+                let summary = match (summary, self.last_mover) {
+                    (GameSummary::CrossWin, Some(Tile::Cross)) => GameSummary::NoughtWin,
+                    (GameSummary::CrossWin, Some(Tile::Nought)) => GameSummary::CrossWin,
+                    (summary, _) => summary,
+                };
+                return (summary, line);
+            }
+            // the roles decide who won, whatever the rules
+            GameBoard::OrderChaos(board) => return board.summary_with_line(),
+        };
+        (self.rules.apply(summary, self.last_mover), line)
+    }
+
+    /// every tile laid out on a single board, for display
+    pub fn tiles(&self) -> Board {
+        match &self.board {
+            GameBoard::Standard(board) | GameBoard::Gravity(board) => board.clone(),
+            GameBoard::Ultimate(board) => board.tiles(),
+            GameBoard::Notakto(board) => board.tiles(),
+            GameBoard::Gomoku(board) => board.board.clone(),
+            GameBoard::Qubic(board) => board.tiles(),
+            GameBoard::Quantum(board) => board.tiles(),
+            GameBoard::Infinite(board) => board.board.clone(),
+            GameBoard::Gobblet(board) => board.tiles(),
+            GameBoard::OrderChaos(board) => board.board.clone(),
+            GameBoard::Numerical(board) => board.tiles(),
+        }
+    }
+
+    /// checks the position can be played
+    ///
+    /// # Errors
+    ///
+    /// Returns the rule the move breaks.
+    pub fn check_move(&self, pos: Position) -> Result<(), MoveError> {
+        match &self.board {
+            GameBoard::Standard(board) => board.check_move(pos),
+            GameBoard::Ultimate(board) => board.check_move(pos),
+            GameBoard::Notakto(board) => board.check_move(pos),
+            GameBoard::Gomoku(board) => board.check_move(pos),
+            GameBoard::Gravity(board) => board.check_drop(pos),
+            GameBoard::Qubic(board) => board.check_move(pos),
+            GameBoard::Quantum(board) => board.check_move(pos),
+            GameBoard::Infinite(board) => board.board.check_move(pos),
+            GameBoard::Gobblet(board) => board.check_move(pos),
+            GameBoard::OrderChaos(board) => board.check_move(pos),
+            GameBoard::Numerical(board) => board.check_move(pos),
+        }
+    }
+
+    /// checks the symbol can be placed at the position, including any restrictions on who may
+    /// place it there
+    ///
+    /// # Errors
+    ///
+    /// Returns the rule the move breaks.
+    pub fn check_placement(&self, pos: Position, symbol: Tile) -> Result<(), MoveError> {
+        match &self.board {
+            GameBoard::Gomoku(board) => board.check_placement(pos, symbol),
+            _ => self.check_move(pos),
+        }
+    }
+
+    /// where a piece dropped in the column lands
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if pieces don't fall in this mode, or the column is off the board or full.
+    pub fn landing(&self, column: Column) -> Result<Position, MoveError> {
+        match &self.board {
+            GameBoard::Gravity(board) => board.landing(column),
+            _ => Err(MoveError::NoGravity),
+        }
+    }
+
+    /// whether the position can be played
+    pub fn is_legal(&self, pos: Position) -> bool {
+        self.check_move(pos).is_ok()
+    }
+
+    /// every position that can be played
+    pub fn legal_moves(&self) -> Vec<Position> {
+        match &self.board {
+            GameBoard::Standard(board) => board.legal_moves(),
+            GameBoard::Ultimate(board) => board.legal_moves(),
+            GameBoard::Notakto(board) => board.legal_moves(),
+            GameBoard::Gomoku(board) => board.legal_moves(),
+            GameBoard::Gravity(board) => board.landings(),
+            GameBoard::Qubic(board) => board.legal_moves(),
+            GameBoard::Quantum(board) => board.legal_moves(),
+            GameBoard::Infinite(board) => board.board.legal_moves(),
+            GameBoard::Gobblet(board) => board.legal_moves(),
+            GameBoard::OrderChaos(board) => board.board.legal_moves(),
+            GameBoard::Numerical(board) => board.legal_moves(),
+        }
+    }
+
+    /// the symbols `player` may place
+    pub const fn symbols(&self, player: Tile) -> &'static [Tile] {
+        match &self.board {
+            GameBoard::Notakto(_) => &[Tile::Cross],
+            GameBoard::OrderChaos(_) => Rules::Wild.symbols(player),
+            _ => self.rules.symbols(player),
+        }
+    }
+
+    /// the symbol placed when `player` doesn't choose one: their own, or a cross in notakto
+    pub fn default_symbol(&self, player: Tile) -> Tile {
+        self.symbols(player)
+            .iter()
+            .copied()
+            .find(|&symbol| symbol == player)
+            .or_else(|| self.symbols(player).first().copied())
+            .unwrap_or(player)
+    }
+
+    /// plays the tile at the position, as the player with that tile
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The game is left unchanged.
+    pub fn play(&mut self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        self.place(pos, tile, tile)
+    }
+
+    /// places `symbol` at the position on behalf of `player`. Only wild games let a player place
+    /// the other symbol.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The game is left unchanged.
+    pub fn place(&mut self, pos: Position, symbol: Tile, player: Tile) -> Result<(), MoveError> {
+        if !self.symbols(player).contains(&symbol) {
+            return Err(if symbol.is_unplayed() {
+                MoveError::Unplayed
+            } else {
+                MoveError::WrongSymbol
+            });
+        }
+        match &mut self.board {
+            GameBoard::Standard(board) => board.play(pos, symbol),
+            GameBoard::Ultimate(board) => board.play(pos, symbol),
+            GameBoard::Notakto(board) => board.play(pos, symbol),
+            GameBoard::Gomoku(board) => board.play(pos, symbol),
+            GameBoard::Gravity(board) => board.play_drop(pos, symbol),
+            GameBoard::Qubic(board) => board.play(pos, symbol),
+            GameBoard::Quantum(_) => Err(MoveError::Paired),
+            GameBoard::Infinite(board) => board.play(pos, symbol),
+            GameBoard::Gobblet(_) => Err(MoveError::NoSize),
+            GameBoard::OrderChaos(board) => board.play(pos, symbol),
+            GameBoard::Numerical(_) => Err(MoveError::NoNumber),
+        }?;
+        self.last_mover = Some(player);
+        Ok(())
+    }
+
+    /// the role of `player` in order and chaos games
+    pub fn role(&self, player: Tile) -> Option<Role> {
+        match &self.board {
+            GameBoard::OrderChaos(board) => board.role(player),
+            _ => None,
+        }
+    }
+
+    /// gives the role that moves first to the seat that starts: order in order and chaos games,
+    /// and the odd numbers in numerical games. Other modes have no roles to assign.
+    pub const fn assign_roles(&mut self, starter: Tile) {
+        match &mut self.board {
+            GameBoard::OrderChaos(board) => board.order = starter,
+            GameBoard::Numerical(board) => board.odd = starter,
+            _ => {}
+        }
+    }
+
+    /// the piece removed when `player` next moves, in infinite games where they already have all
+    /// their pieces on the board. There is none once the game is over.
+    pub fn vanishing(&self, player: Tile) -> Option<Position> {
+        match &self.board {
+            GameBoard::Infinite(board) if !board.summary().is_finished() => {
+                board.vanishing(self.default_symbol(player))
+            }
+            _ => None,
+        }
+    }
+
+    /// the cells the last move can collapse into, in a quantum game where it closed a cycle
+    pub fn pending_collapse(&self) -> Option<[Position; 2]> {
+        match &self.board {
+            GameBoard::Quantum(board) => board.pending_collapse(),
+            _ => None,
+        }
+    }
+
+    /// plays a quantum move on behalf of `player`. Collapsing a cycle is chosen by the opponent of
+    /// whoever made it, so the last mover stays the same.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this isn't a quantum game or the move isn't legal. The game is left
+    /// unchanged.
+    pub fn play_quantum(&mut self, m: QuantumMove, player: Tile) -> Result<(), MoveError> {
+        let GameBoard::Quantum(board) = &mut self.board else {
+            return Err(MoveError::NotQuantum);
+        };
+        match m {
+            QuantumMove::Entangle(a, b) => {
+                board.entangle(a, b, player)?;
+                self.last_mover = Some(player);
+            }
+            QuantumMove::Collapse(cell) => board.collapse(cell)?,
+        }
+        Ok(())
+    }
+
+    /// every quantum move that can be played
+    pub fn quantum_moves(&self) -> Vec<QuantumMove> {
+        match &self.board {
+            GameBoard::Quantum(board) => board.moves(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// checks `player` can make the gobblet move
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this isn't a gobblet game, or the rule the move breaks.
+    pub fn check_gobblet(&self, m: GobbletMove, player: Tile) -> Result<(), MoveError> {
+        match &self.board {
+            GameBoard::Gobblet(board) => board.check_gobblet(m, player),
+            _ => Err(MoveError::NotGobblet),
+        }
+    }
+
+    /// plays a new piece of the size, or moves one already on the board, on behalf of `player`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this isn't a gobblet game or the move isn't legal. The game is left
+    /// unchanged.
+    pub fn play_gobblet(&mut self, m: GobbletMove, player: Tile) -> Result<(), MoveError> {
+        let GameBoard::Gobblet(board) = &mut self.board else {
+            return Err(MoveError::NotGobblet);
+        };
+        board.play(m, player)?;
+        self.last_mover = Some(player);
+        Ok(())
+    }
+
+    /// every gobblet move `player` can make
+    pub fn gobblet_moves(&self, player: Tile) -> Vec<GobbletMove> {
+        match &self.board {
+            GameBoard::Gobblet(board) => board.moves(player),
+            _ => Vec::new(),
+        }
+    }
+
+    /// checks `player` can play the number at the position
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this isn't a numerical game, or the rule the move breaks.
+    pub fn check_number(&self, pos: Position, number: u8, player: Tile) -> Result<(), MoveError> {
+        match &self.board {
+            GameBoard::Numerical(board) => board.check_number(pos, number, player),
+            _ => Err(MoveError::NotNumerical),
+        }
+    }
+
+    /// plays the move on behalf of `player`, whichever mode it is for
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't for this mode or isn't legal. The game is left unchanged.
+    pub fn apply(&mut self, play: Play, player: Tile) -> Result<(), MoveError> {
+        match play {
+            Play::Place { position, symbol } => self.place(position, symbol, player),
+            Play::Quantum(m) => self.play_quantum(m, player),
+            Play::Gobblet(m) => self.play_gobblet(m, player),
+            Play::Number(position, number) => self.play_number(position, number, player),
+        }
+    }
+
+    /// plays one of `player`'s numbers at the position
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this isn't a numerical game or the move isn't legal. The game is left
+    /// unchanged.
+    pub fn play_number(
+        &mut self,
+        pos: Position,
+        number: u8,
+        player: Tile,
+    ) -> Result<(), MoveError> {
+        let GameBoard::Numerical(board) = &mut self.board else {
+            return Err(MoveError::NotNumerical);
+        };
+        board.play(pos, number, player)?;
+        self.last_mover = Some(player);
+        Ok(())
+    }
+
+    /// every position and number `player` can play
+    pub fn number_moves(&self, player: Tile) -> Vec<(Position, u8)> {
+        match &self.board {
+            GameBoard::Numerical(board) if !board.summary().is_finished() => board.moves(player),
+            _ => Vec::new(),
+        }
+    }
+
+    /// every position and symbol `player` can play
+    pub fn placements(&self, player: Tile) -> Vec<(Position, Tile)> {
+        let symbols = self.symbols(player);
+        self.legal_moves()
+            .into_iter()
+            .flat_map(|pos| symbols.iter().map(move |&symbol| (pos, symbol)))
+            .filter(|&(pos, symbol)| self.check_placement(pos, symbol).is_ok())
+            .collect()
+    }
+
+    /// checks the game could have been reached by playing it
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), InvalidBoard> {
+        match &self.board {
+            GameBoard::Standard(board) => board.validate_with(self.rules),
+            GameBoard::Ultimate(board) => board.validate_with(self.rules),
+            GameBoard::Notakto(board) => board.validate(),
+            GameBoard::Gomoku(board) => board.validate_with(self.rules),
+            GameBoard::Gravity(board) => board.validate_gravity(self.rules),
+            GameBoard::Qubic(board) => board.validate_with(self.rules),
+            GameBoard::Quantum(board) => board.validate_with(self.rules),
+            GameBoard::Infinite(board) => board.validate_with(self.rules),
+            GameBoard::Gobblet(board) => board.validate(),
+            GameBoard::OrderChaos(board) => board.validate(),
+            GameBoard::Numerical(board) => board.validate(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gobblet::Size, testing::pos};
+
+    #[test]
+    fn test_misere() {
+        let mut game = Game::new(&GameOptions::try_from("rules=misere").unwrap());
+        for (xy, tile) in [
+            ((0, 0), Tile::Cross),
+            ((1, 0), Tile::Nought),
+            ((0, 1), Tile::Cross),
+            ((1, 1), Tile::Nought),
+        ] {
+            game.play(xy.try_into().unwrap(), tile).unwrap();
+        }
+        assert_eq!(game.summary(), GameSummary::InProgress);
+
+        game.play((0, 2).try_into().unwrap(), Tile::Cross).unwrap();
+        let (summary, line) = game.summary_with_line();
+        assert_eq!(summary, GameSummary::NoughtWin);
+        assert_eq!(line.map(|line| line.len()), Some(3));
+    }
+
+    #[test]
+    fn test_wild() {
+        let mut game = Game::new(&GameOptions::try_from("rules=wild").unwrap());
+        for (xy, symbol, player) in [
+            ((0, 0), Tile::Nought, Tile::Cross),
+            ((1, 1), Tile::Cross, Tile::Nought),
+            ((0, 1), Tile::Nought, Tile::Cross),
+        ] {
+            game.place(xy.try_into().unwrap(), symbol, player).unwrap();
+        }
+        assert_eq!(game.summary(), GameSummary::InProgress);
+        assert_eq!(game.placements(Tile::Nought).len(), 12);
+
+        // noughts completes a line of noughts started by crosses
+        game.place((0, 2).try_into().unwrap(), Tile::Nought, Tile::Nought)
+            .unwrap();
+        assert_eq!(game.summary(), GameSummary::NoughtWin);
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_notakto() {
+        let mut game = Game::new(&GameOptions::try_from("mode=notakto").unwrap());
+        assert_eq!(game.default_symbol(Tile::Nought), Tile::Cross);
+        assert_eq!(
+            game.play((0, 0).try_into().unwrap(), Tile::Nought),
+            Err(MoveError::WrongSymbol)
+        );
+
+        // kill every board but the last, taking turns
+        let mut player = Tile::Cross;
+        for x in [0, 3, 6] {
+            for y in 0..3 {
+                game.place((x, y).try_into().unwrap(), Tile::Cross, player)
+                    .unwrap();
+                player = player.opponent();
+            }
+        }
+        // crosses completed the last line, so noughts wins
+        assert_eq!(game.last_mover, Some(Tile::Cross));
+        assert_eq!(game.summary(), GameSummary::NoughtWin);
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_gravity() {
+        let mut game = Game::new(&GameOptions::try_from("mode=gravity&size=7,6,4").unwrap());
+        assert_eq!(game.legal_moves().len(), 7);
+        for _ in 0..3 {
+            for (x, tile) in [(0, Tile::Cross), (1, Tile::Nought)] {
+                let pos = game.landing(Column(x)).unwrap();
+                game.play(pos, tile).unwrap();
+            }
+        }
+        assert_eq!(
+            game.play((2, 0).try_into().unwrap(), Tile::Cross),
+            Err(MoveError::Floating)
+        );
+        let pos = game.landing(Column(0)).unwrap();
+        assert_eq!(pos, (0, 2).try_into().unwrap());
+        game.play(pos, Tile::Cross).unwrap();
+        assert_eq!(game.summary(), GameSummary::CrossWin);
+        assert_eq!(game.validate(), Ok(()));
+        assert_eq!(
+            Game::default().landing(Column(0)),
+            Err(MoveError::NoGravity)
+        );
+    }
+
+    #[test]
+    fn test_qubic() {
+        let mut game = Game::new(&GameOptions::try_from("mode=qubic").unwrap());
+        assert_eq!(game.legal_moves().len(), 64);
+        assert_eq!(game.tiles().width(), 16);
+        // a column through every layer
+        for (x, tile) in [(2, Tile::Cross), (3, Tile::Nought)] {
+            for layer in 0..3 {
+                game.play((layer * 4 + x, 1).try_into().unwrap(), tile)
+                    .unwrap();
+            }
+        }
+        game.play((14, 1).try_into().unwrap(), Tile::Cross).unwrap();
+        assert_eq!(game.summary(), GameSummary::CrossWin);
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_quantum() {
+        let mut game = Game::new(&GameOptions::try_from("mode=quantum").unwrap());
+        assert_eq!(game.play(pos(0, 0), Tile::Cross), Err(MoveError::Paired));
+        for (a, b, tile) in [
+            (pos(0, 0), pos(1, 1), Tile::Cross),
+            (pos(1, 1), pos(0, 0), Tile::Nought),
+        ] {
+            game.play_quantum(QuantumMove::Entangle(a, b), tile)
+                .unwrap();
+        }
+        assert_eq!(game.pending_collapse(), Some([pos(1, 1), pos(0, 0)]));
+        assert_eq!(game.last_mover, Some(Tile::Nought));
+        game.play_quantum(QuantumMove::Collapse(pos(0, 0)), Tile::Cross)
+            .unwrap();
+        assert_eq!(game.tiles().get(pos(1, 1)), Some(Tile::Cross));
+        assert_eq!(game.last_mover, Some(Tile::Nought));
+        assert_eq!(game.validate(), Ok(()));
+        assert_eq!(
+            Game::default().play_quantum(QuantumMove::Collapse(pos(0, 0)), Tile::Cross),
+            Err(MoveError::NotQuantum)
+        );
+    }
+
+    #[test]
+    fn test_infinite() {
+        let mut game = Game::new(&GameOptions::try_from("mode=infinite").unwrap());
+        let mut player = Tile::Cross;
+        for xy in [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)] {
+            game.play(xy.try_into().unwrap(), player).unwrap();
+            player = player.opponent();
+        }
+        assert_eq!(
+            game.vanishing(Tile::Cross),
+            Some((0, 0).try_into().unwrap())
+        );
+        assert_eq!(Game::default().vanishing(Tile::Cross), None);
+
+        game.play((1, 2).try_into().unwrap(), Tile::Cross).unwrap();
+        assert_eq!(
+            game.tiles().get((0, 0).try_into().unwrap()),
+            Some(Tile::Unplayed)
+        );
+        assert_eq!(game.legal_moves().len(), 3);
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_gobblet() {
+        let mut game = Game::new(&GameOptions::try_from("mode=gobblet").unwrap());
+        let pos = (1, 1).try_into().unwrap();
+        assert_eq!(game.play(pos, Tile::Cross), Err(MoveError::NoSize));
+        let m = GobbletMove {
+            size: Size::Large,
+            from: None,
+            to: pos,
+        };
+        game.play_gobblet(m, Tile::Cross).unwrap();
+        assert_eq!(game.last_mover, Some(Tile::Cross));
+        assert_eq!(game.tiles().get(pos), Some(Tile::Cross));
+        assert_eq!(game.check_move(pos), Err(MoveError::Occupied));
+        assert_eq!(game.legal_moves().len(), 8);
+        assert_eq!(
+            Game::default().play_gobblet(m, Tile::Cross),
+            Err(MoveError::NotGobblet)
+        );
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_order_chaos() {
+        let mut game = Game::new(&GameOptions::try_from("mode=orderchaos&rules=misere").unwrap());
+        game.assign_roles(Tile::Nought);
+        assert_eq!(game.role(Tile::Nought), Some(Role::Order));
+        assert_eq!(game.role(Tile::Cross), Some(Role::Chaos));
+        assert_eq!(game.placements(Tile::Cross).len(), 72);
+
+        for x in 0..5 {
+            let (player, symbol) = if x % 2 == 0 {
+                (Tile::Nought, Tile::Cross)
+            } else {
+                (Tile::Cross, Tile::Cross)
+            };
+            game.place((x, 0).try_into().unwrap(), symbol, player)
+                .unwrap();
+        }
+        // chaos completed a line of crosses, which is a win for order whatever the rules
+        assert_eq!(game.summary(), GameSummary::NoughtWin);
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_numerical() {
+        let mut game = Game::new(&GameOptions::try_from("mode=numerical").unwrap());
+        game.assign_roles(Tile::Nought);
+        let pos = (1, 1).try_into().unwrap();
+        assert_eq!(game.play(pos, Tile::Nought), Err(MoveError::NoNumber));
+        assert_eq!(
+            game.play_number(pos, 4, Tile::Nought),
+            Err(MoveError::WrongParity)
+        );
+        game.play_number(pos, 5, Tile::Nought).unwrap();
+        assert_eq!(game.last_mover, Some(Tile::Nought));
+        assert_eq!(game.tiles().get(pos), Some(Tile::Nought));
+        assert_eq!(game.number_moves(Tile::Cross).len(), 8 * 4);
+        assert_eq!(
+            Game::default().play_number(pos, 5, Tile::Cross),
+            Err(MoveError::NotNumerical)
+        );
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_own_symbol_only() {
+        let mut game = Game::default();
+        let pos = (0, 0).try_into().unwrap();
+        assert_eq!(
+            game.place(pos, Tile::Nought, Tile::Cross),
+            Err(MoveError::WrongSymbol)
+        );
+        assert_eq!(
+            game.place(pos, Tile::Unplayed, Tile::Cross),
+            Err(MoveError::Unplayed)
+        );
+        assert_eq!(game.place(pos, Tile::Cross, Tile::Cross), Ok(()));
+        assert_eq!(game.last_mover, Some(Tile::Cross));
+    }
+}
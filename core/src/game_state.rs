@@ -58,40 +58,102 @@ impl fmt::Display for GameID {
     }
 }
 
+#[derive(Error, Debug)]
+pub enum SeatTokenParseError {
+    #[error("invalid length")]
+    InvalidLength,
+    #[error("must be alphanumeric")]
+    InvalidCharacters,
+}
+
+/// a secret handed to a player when they take a seat, used to reclaim the seat after reconnecting
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SeatToken(String);
+
+impl Default for SeatToken {
+    fn default() -> Self {
+        let r = rand::thread_rng();
+        let s = r
+            .sample_iter(rand::distributions::Alphanumeric)
+            .take(Self::LENGTH)
+            .map(char::from)
+            .collect();
+
+        Self(s)
+    }
+}
+
+impl TryFrom<&str> for SeatToken {
+    type Error = SeatTokenParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.len() == Self::LENGTH {
+            if value.chars().all(char::is_alphanumeric) {
+                Ok(Self(value.to_string()))
+            } else {
+                Err(Self::Error::InvalidCharacters)
+            }
+        } else {
+            Err(Self::Error::InvalidLength)
+        }
+    }
+}
+
+impl SeatToken {
+    const LENGTH: usize = 24;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Display for SeatToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum PositionParseError {
-    #[error("x must be less than 3")]
+    #[error("x must be less than 16")]
     XOutOfBounds,
-    #[error("y must be less than 3")]
+    #[error("y must be less than 16")]
     YOutOfBounds,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Position(u8);
 
 impl TryFrom<(usize, usize)> for Position {
     type Error = PositionParseError;
 
     fn try_from((x, y): (usize, usize)) -> Result<Self, Self::Error> {
-        if x >= 3 {
+        if x >= Self::MAX {
             Err(Self::Error::XOutOfBounds)
-        } else if y >= 3 {
+        } else if y >= Self::MAX {
             Err(Self::Error::YOutOfBounds)
         } else {
             // truncation is intentional. We only want to return specific bits.
             #[allow(clippy::cast_possible_truncation)]
-            Ok(Self(((x as u8) << 6) + ((y as u8) << 4)))
+            Ok(Self::new(x as u8, y as u8))
         }
     }
 }
 
 impl Position {
-    // x is the first 2 bits
+    /// coordinates must be less than this to fit in 4 bits
+    pub const MAX: usize = 16;
+
+    // x and y must already be known to be less than MAX
+    pub(crate) const fn new(x: u8, y: u8) -> Self {
+        Self((x << 4) + y)
+    }
+    // x is the first 4 bits
     pub const fn x(&self) -> usize {
-        (self.0 >> 6) as usize
+        (self.0 >> 4) as usize
     }
-    // y is the second set of 2 bits
+    // y is the second set of 4 bits
     pub const fn y(&self) -> usize {
-        ((self.0 << 2) >> 6) as usize
+        (self.0 & 0x0f) as usize
     }
 }
@@ -0,0 +1,343 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::Position, Board, GameSummary, MoveError, Tile};
+
+/// how big a gobblet piece is. A piece can cover any smaller one.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Size {
+    Small,
+    Medium,
+    Large,
+}
+
+impl Size {
+    pub const ALL: [Self; 3] = [Self::Small, Self::Medium, Self::Large];
+}
+
+/// a piece in a gobblet stack
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub tile: Tile,
+    pub size: Size,
+}
+
+/// a move in a gobblet game
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct GobbletMove {
+    pub size: Size,
+    /// the cell the piece is moved from, or none to play a new piece
+    pub from: Option<Position>,
+    pub to: Position,
+}
+
+/// a 3x3 board where each player has two pieces of each size, and a larger piece may be placed
+/// over a smaller one of either symbol
+///
+/// Pieces already on the board can be moved to another cell, which may uncover a line for the
+/// opponent. Only the top piece of each stack counts towards a line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct GobbletBoard {
+    /// the pieces in each cell, bottom first
+    pub stacks: Vec<Vec<Vec<Piece>>>,
+    /// the player who moved last, who loses if their move uncovers a line for both players
+    pub last_mover: Option<Tile>,
+}
+
+impl Default for GobbletBoard {
+    fn default() -> Self {
+        Self {
+            stacks: vec![vec![Vec::new(); 3]; 3],
+            last_mover: None,
+        }
+    }
+}
+
+impl GobbletBoard {
+    /// how many pieces of each size a player has
+    pub const PIECES: usize = 2;
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// the pieces in the cell, bottom first
+    pub fn stack(&self, cell: Position) -> Option<&[Piece]> {
+        self.stacks
+            .get(cell.x())
+            .and_then(|column| column.get(cell.y()))
+            .map(Vec::as_slice)
+    }
+
+    /// the visible piece in the cell
+    pub fn top(&self, cell: Position) -> Option<Piece> {
+        self.stack(cell).and_then(|stack| stack.last()).copied()
+    }
+
+    /// how many pieces of the size `tile` has yet to play
+    pub fn reserve(&self, tile: Tile, size: Size) -> usize {
+        let played = self
+            .stacks
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|&&piece| piece == Piece { tile, size })
+            .count();
+        Self::PIECES.saturating_sub(played)
+    }
+
+    /// the top piece of each cell
+    pub fn tiles(&self) -> Board {
+        let mut tiles = Board::new();
+        for cell in Board::new().positions() {
+            if let Some(piece) = self.top(cell) {
+                tiles.tiles[cell.x()][cell.y()] = piece.tile;
+            }
+        }
+        tiles
+    }
+
+    /// the first line of `tile` on top of the stacks
+    pub(crate) fn line(&self, tile: Tile) -> Option<&[Position; 3]> {
+        Board::LINES.iter().find(|line| {
+            line.iter()
+                .all(|&cell| self.top(cell).is_some_and(|piece| piece.tile == tile))
+        })
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary, and the winning line if there is one. When a move uncovers a line for the
+    /// opponent, they win even if the mover also made one.
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        let order = match self.last_mover {
+            Some(Tile::Cross) => [Tile::Nought, Tile::Cross],
+            _ => [Tile::Cross, Tile::Nought],
+        };
+        if let Some((tile, line)) = order
+            .into_iter()
+            .find_map(|tile| self.line(tile).map(|line| (tile, line)))
+        {
+            let summary = if tile.is_cross() {
+                GameSummary::CrossWin
+            } else {
+                GameSummary::NoughtWin
+            };
+            return (summary, Some(line.to_vec()));
+        }
+        // the player to move may be left with nothing they can play
+        match self.last_mover {
+            Some(tile) if !self.has_move(tile.opponent()) => (GameSummary::Tie, None),
+            _ => (GameSummary::InProgress, None),
+        }
+    }
+
+    /// checks a piece of some size could be placed in the cell
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_move(&self, cell: Position) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
+        }
+        match self.stack(cell) {
+            None => Err(MoveError::OutOfBounds),
+            Some([.., top]) if top.size == Size::Large => Err(MoveError::Occupied),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// every cell a piece could be placed in
+    pub fn legal_moves(&self) -> Vec<Position> {
+        self.tiles()
+            .positions()
+            .filter(|&cell| self.check_move(cell).is_ok())
+            .collect()
+    }
+
+    /// checks the move against the rules for pieces, ignoring whether the game is over
+    fn check_pieces(&self, m: GobbletMove, player: Tile) -> Result<(), MoveError> {
+        if player.is_unplayed() {
+            return Err(MoveError::Unplayed);
+        }
+        let top = self.stack(m.to).ok_or(MoveError::OutOfBounds)?.last();
+        match m.from {
+            Some(from) => {
+                if self.top(from)
+                    != Some(Piece {
+                        tile: player,
+                        size: m.size,
+                    })
+                {
+                    return Err(MoveError::NotYourPiece);
+                }
+            }
+            None => {
+                if self.reserve(player, m.size) == 0 {
+                    return Err(MoveError::NoPiece);
+                }
+            }
+        }
+        match top {
+            Some(top) if top.size >= m.size => Err(MoveError::TooSmall),
+            _ => Ok(()),
+        }
+    }
+
+    /// checks `player` can make the move
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_gobblet(&self, m: GobbletMove, player: Tile) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
+        }
+        self.check_pieces(m, player)
+    }
+
+    /// every move that follows the rules for pieces, whether or not the game is over
+    fn candidates(&self, player: Tile) -> impl Iterator<Item = GobbletMove> + '_ {
+        let cells = self.tiles().positions().collect::<Vec<_>>();
+        let froms = std::iter::once(None).chain(cells.clone().into_iter().map(Some));
+        froms
+            .flat_map(move |from| Size::ALL.into_iter().map(move |size| (from, size)))
+            .flat_map(move |(from, size)| {
+                cells
+                    .clone()
+                    .into_iter()
+                    .map(move |to| GobbletMove { size, from, to })
+            })
+            .filter(move |&m| self.check_pieces(m, player).is_ok())
+    }
+
+    fn has_move(&self, player: Tile) -> bool {
+        self.candidates(player).next().is_some()
+    }
+
+    /// every move `player` can make
+    pub fn moves(&self, player: Tile) -> Vec<GobbletMove> {
+        if self.summary().is_finished() {
+            return Vec::new();
+        }
+        self.candidates(player).collect()
+    }
+
+    /// plays a new piece, or moves one of `player`'s pieces from the top of a stack
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn play(&mut self, m: GobbletMove, player: Tile) -> Result<(), MoveError> {
+        self.check_gobblet(m, player)?;
+        let piece = match m.from {
+            Some(from) => self.stacks[from.x()][from.y()]
+                .pop()
+                .ok_or(MoveError::NotYourPiece)?,
+            None => Piece {
+                tile: player,
+                size: m.size,
+            },
+        };
+        self.stacks[m.to.x()][m.to.y()].push(piece);
+        self.last_mover = Some(player);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pos;
+
+    fn place(size: Size, x: usize, y: usize) -> GobbletMove {
+        GobbletMove {
+            size,
+            from: None,
+            to: pos(x, y),
+        }
+    }
+
+    #[test]
+    fn test_larger_covers_smaller() {
+        let mut board = GobbletBoard::default();
+        board.play(place(Size::Medium, 1, 1), Tile::Cross).unwrap();
+        assert_eq!(
+            board.play(place(Size::Medium, 1, 1), Tile::Nought),
+            Err(MoveError::TooSmall)
+        );
+        board.play(place(Size::Large, 1, 1), Tile::Nought).unwrap();
+        assert_eq!(
+            board.top(pos(1, 1)),
+            Some(Piece {
+                tile: Tile::Nought,
+                size: Size::Large
+            })
+        );
+        assert_eq!(board.stack(pos(1, 1)).map(<[_]>::len), Some(2));
+        assert_eq!(board.check_move(pos(1, 1)), Err(MoveError::Occupied));
+
+        board.play(place(Size::Large, 0, 0), Tile::Cross).unwrap();
+        board.play(place(Size::Large, 2, 2), Tile::Nought).unwrap();
+        assert_eq!(board.reserve(Tile::Nought, Size::Large), 0);
+        assert_eq!(
+            board.play(place(Size::Large, 2, 0), Tile::Nought),
+            Err(MoveError::NoPiece)
+        );
+        assert_eq!(board.summary(), GameSummary::InProgress);
+    }
+
+    #[test]
+    fn test_relocation() {
+        let mut board = GobbletBoard::default();
+        board.play(place(Size::Small, 0, 0), Tile::Cross).unwrap();
+        board.play(place(Size::Large, 0, 0), Tile::Nought).unwrap();
+        let relocate = GobbletMove {
+            size: Size::Large,
+            from: Some(pos(0, 0)),
+            to: pos(2, 2),
+        };
+        assert_eq!(
+            board.play(relocate, Tile::Cross),
+            Err(MoveError::NotYourPiece)
+        );
+        board.play(place(Size::Small, 1, 0), Tile::Cross).unwrap();
+        board.play(relocate, Tile::Nought).unwrap();
+        assert_eq!(
+            board.top(pos(0, 0)).map(|piece| piece.tile),
+            Some(Tile::Cross)
+        );
+        assert_eq!(board.reserve(Tile::Nought, Size::Large), 1);
+    }
+
+    #[test]
+    fn test_uncovered_line_wins_for_opponent() {
+        let mut board = GobbletBoard::default();
+        for (m, tile) in [
+            (place(Size::Small, 0, 0), Tile::Cross),
+            (place(Size::Large, 0, 0), Tile::Nought),
+            (place(Size::Medium, 0, 1), Tile::Cross),
+            (place(Size::Large, 1, 1), Tile::Nought),
+            (place(Size::Medium, 0, 2), Tile::Cross),
+            (place(Size::Medium, 1, 0), Tile::Nought),
+            (place(Size::Small, 2, 1), Tile::Cross),
+        ] {
+            board.play(m, tile).unwrap();
+        }
+        assert_eq!(board.summary(), GameSummary::InProgress);
+
+        // moving the large nought completes a column for noughts, but uncovers one for crosses
+        let lift = GobbletMove {
+            size: Size::Large,
+            from: Some(pos(0, 0)),
+            to: pos(1, 2),
+        };
+        board.play(lift, Tile::Nought).unwrap();
+        let (summary, line) = board.summary_with_line();
+        assert_eq!(summary, GameSummary::CrossWin);
+        assert_eq!(line, Some(vec![pos(0, 0), pos(0, 1), pos(0, 2)]));
+        assert_eq!(board.moves(Tile::Cross), []);
+    }
+}
@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{game_state::Position, Board, BoardSize, GameSummary, MoveError, Tile};
+
+#[derive(Error, Debug)]
+pub enum RestrictionParseError {
+    #[error("must be one of freestyle or renju")]
+    Unknown,
+}
+
+/// limits on the first player, who otherwise has a large advantage
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Restriction {
+    /// five or more in a row wins for either player
+    #[default]
+    Freestyle,
+    /// the first player may not make an overline of six or more, or two threes at once, unless the
+    /// move also makes five
+    Renju,
+}
+
+impl TryFrom<&str> for Restriction {
+    type Error = RestrictionParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "freestyle" => Ok(Self::Freestyle),
+            "renju" => Ok(Self::Renju),
+            _ => Err(Self::Error::Unknown),
+        }
+    }
+}
+
+/// a 15x15 board where five in a row wins. Only the lines through each move are checked, and the
+/// winning line is kept, so finding the result doesn't search the whole board.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct GomokuBoard {
+    pub board: Board,
+    pub restriction: Restriction,
+    /// the tile that moved first, once anyone has moved. Renju restrictions apply to it.
+    pub first: Option<Tile>,
+    /// the five or more in a row that won the game
+    pub winning_line: Option<Vec<Position>>,
+}
+
+impl Default for GomokuBoard {
+    fn default() -> Self {
+        Self::new(Restriction::default())
+    }
+}
+
+impl GomokuBoard {
+    pub const SIZE: BoardSize = BoardSize {
+        width: 15,
+        height: 15,
+        win_length: 5,
+    };
+
+    pub fn new(restriction: Restriction) -> Self {
+        Self {
+            board: Board::with_size(Self::SIZE),
+            restriction,
+            first: None,
+            winning_line: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.board.clear();
+        self.first = None;
+        self.winning_line = None;
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary, and the line that won the game if there is one
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        if let Some(line) = &self.winning_line {
+            let summary = match line.first().and_then(|&pos| self.board.get(pos)) {
+                Some(Tile::Cross) => GameSummary::CrossWin,
+                Some(Tile::Nought) => GameSummary::NoughtWin,
+                _ => GameSummary::InProgress,
+            };
+            return (summary, Some(line.clone()));
+        }
+
+        if self
+            .board
+            .tiles
+            .iter()
+            .flatten()
+            .all(|tile| !tile.is_unplayed())
+        {
+            (GameSummary::Tie, None)
+        } else {
+            (GameSummary::InProgress, None)
+        }
+    }
+
+    /// the position `i` steps along the direction, if it is on the board
+    fn step(&self, pos: Position, (dx, dy): (isize, isize), i: isize) -> Option<Position> {
+        let x = pos.x().checked_add_signed(dx * i)?;
+        let y = pos.y().checked_add_signed(dy * i)?;
+        // positions on the board are less than 16, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        (x < self.board.width() && y < self.board.height()).then(|| Position::new(x as u8, y as u8))
+    }
+
+    /// the unbroken row of `tile` through `pos` along the direction, in order, counting `pos` and
+    /// the `extra` positions as `tile`
+    fn run(
+        &self,
+        pos: Position,
+        direction: (isize, isize),
+        tile: Tile,
+        extra: &[Position],
+    ) -> Vec<Position> {
+        let is_tile =
+            |p: Position| p == pos || extra.contains(&p) || self.board.get(p) == Some(tile);
+        let side = |sign: isize| {
+            (1..)
+                .map_while(|i| self.step(pos, direction, sign * i).filter(|&p| is_tile(p)))
+                .collect::<Vec<_>>()
+        };
+
+        let mut run = side(-1);
+        run.reverse();
+        run.push(pos);
+        run.extend(side(1));
+        run
+    }
+
+    /// whether the renju restrictions forbid `tile` being played at the position
+    fn is_forbidden(&self, pos: Position, tile: Tile) -> bool {
+        if self.restriction != Restriction::Renju || self.first.is_some_and(|first| first != tile) {
+            return false;
+        }
+
+        let runs = Board::DIRECTIONS.map(|direction| self.run(pos, direction, tile, &[]).len());
+        // making five wins, even when the move makes a forbidden shape too
+        if runs.contains(&5) {
+            return false;
+        }
+        runs.iter().any(|&n| n > 5) || self.threes(pos, tile) >= 2
+    }
+
+    /// the number of directions in which playing at the position makes a three: a row that one more
+    /// move turns into an open four, with unplayed tiles at both ends
+    fn threes(&self, pos: Position, tile: Tile) -> usize {
+        Board::DIRECTIONS
+            .into_iter()
+            .filter(|&direction| {
+                (-4..=4)
+                    .filter_map(|i| self.step(pos, direction, i))
+                    .filter(|&gap| self.board.get(gap) == Some(Tile::Unplayed) && gap != pos)
+                    .any(|gap| {
+                        let run = self.run(gap, direction, tile, &[pos]);
+                        let open = |end: Option<Position>| {
+                            end.and_then(|end| self.board.get(end))
+                                .is_some_and(|tile| tile.is_unplayed())
+                        };
+                        run.len() == 4
+                            && run.contains(&pos)
+                            && open(self.step(run[0], direction, -1))
+                            && open(self.step(run[3], direction, 1))
+                    })
+            })
+            .count()
+    }
+
+    /// checks the position can be played by someone
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the game is over, or the position is off the board or already played.
+    pub fn check_move(&self, pos: Position) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
+        }
+        match self.board.get(pos) {
+            None => Err(MoveError::OutOfBounds),
+            Some(Tile::Unplayed) => Ok(()),
+            Some(_) => Err(MoveError::Occupied),
+        }
+    }
+
+    /// checks the tile can be played at the position, including the renju restrictions
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_placement(&self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        self.check_move(pos)?;
+        if tile.is_unplayed() {
+            return Err(MoveError::Unplayed);
+        }
+        if self.is_forbidden(pos, tile) {
+            return Err(MoveError::Forbidden);
+        }
+        Ok(())
+    }
+
+    /// every position that can be played by someone. The first player may be forbidden from some
+    /// of them under renju restrictions.
+    pub fn legal_moves(&self) -> Vec<Position> {
+        if self.summary().is_finished() {
+            return Vec::new();
+        }
+        self.board
+            .positions()
+            .filter(|&pos| self.board.get(pos) == Some(Tile::Unplayed))
+            .collect()
+    }
+
+    /// plays the tile, and keeps the line if it wins
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn play(&mut self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        self.check_placement(pos, tile)?;
+        self.board.tiles[pos.x()][pos.y()] = tile;
+        self.first.get_or_insert(tile);
+
+        let runs = Board::DIRECTIONS.map(|direction| self.run(pos, direction, tile, &[]));
+        // exactly five is preferred, since it is the only win a restricted player can make
+        self.winning_line = runs
+            .iter()
+            .find(|run| run.len() == Self::SIZE.win_length)
+            .or_else(|| runs.iter().find(|run| run.len() > Self::SIZE.win_length))
+            .cloned();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pos;
+
+    /// plays the tiles without checking the rules
+    fn board(restriction: Restriction, crosses: &[(usize, usize)]) -> GomokuBoard {
+        let mut board = GomokuBoard::new(restriction);
+        for &(x, y) in crosses {
+            board.board.tiles[x][y] = Tile::Cross;
+        }
+        board.first = Some(Tile::Cross);
+        board
+    }
+
+    #[test]
+    fn test_five_in_a_row() {
+        let mut board = GomokuBoard::default();
+        for x in 3..7 {
+            board.play(pos(x, x), Tile::Cross).unwrap();
+            assert_eq!(board.summary(), GameSummary::InProgress);
+        }
+        board.play(pos(7, 7), Tile::Cross).unwrap();
+        let (summary, line) = board.summary_with_line();
+        assert_eq!(summary, GameSummary::CrossWin);
+        assert_eq!(line, Some((3..8).map(|x| pos(x, x)).collect()));
+        assert_eq!(board.check_move(pos(0, 0)), Err(MoveError::GameOver));
+        assert_eq!(board.legal_moves(), []);
+    }
+
+    #[test]
+    fn test_freestyle_overline_wins() {
+        let mut board = board(
+            Restriction::Freestyle,
+            &[(0, 0), (1, 0), (2, 0), (4, 0), (5, 0)],
+        );
+        board.play(pos(3, 0), Tile::Cross).unwrap();
+        assert_eq!(board.winning_line.map(|line| line.len()), Some(6));
+    }
+
+    #[test]
+    fn test_renju_overline_is_forbidden() {
+        let board = board(
+            Restriction::Renju,
+            &[(0, 0), (1, 0), (2, 0), (4, 0), (5, 0)],
+        );
+        assert_eq!(
+            board.check_placement(pos(3, 0), Tile::Cross),
+            Err(MoveError::Forbidden)
+        );
+        // the restrictions only apply to the first player
+        assert_eq!(board.check_placement(pos(3, 0), Tile::Nought), Ok(()));
+    }
+
+    #[test]
+    fn test_renju_double_three_is_forbidden() {
+        let board = board(Restriction::Renju, &[(5, 7), (6, 7), (7, 5), (7, 6)]);
+        assert_eq!(
+            board.check_placement(pos(7, 7), Tile::Cross),
+            Err(MoveError::Forbidden)
+        );
+        // a single three is allowed
+        assert_eq!(board.check_placement(pos(4, 7), Tile::Cross), Ok(()));
+        let freestyle = GomokuBoard {
+            restriction: Restriction::Freestyle,
+            ..board
+        };
+        assert_eq!(freestyle.check_placement(pos(7, 7), Tile::Cross), Ok(()));
+    }
+
+    #[test]
+    fn test_blocked_three_isnt_a_three() {
+        let mut board = board(Restriction::Renju, &[(5, 7), (6, 7), (7, 5), (7, 6)]);
+        board.board.tiles[4][7] = Tile::Nought;
+        board.board.tiles[8][7] = Tile::Nought;
+        assert_eq!(board.check_placement(pos(7, 7), Tile::Cross), Ok(()));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Restriction::try_from("renju").unwrap(), Restriction::Renju);
+        assert!(Restriction::try_from("pro").is_err());
+    }
+}
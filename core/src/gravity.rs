@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::Position, Board, MoveError, Tile};
+
+/// a column of the board, for games where pieces fall to the bottom. The bottom is the last row.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Column(pub u8);
+
+impl Column {
+    pub const fn x(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<Position> for Column {
+    fn from(pos: Position) -> Self {
+        // positions are less than 16, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        Self(pos.x() as u8)
+    }
+}
+
+impl Board {
+    /// where a piece dropped in the column lands: the lowest unplayed tile
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the column is off the board or full.
+    pub fn landing(&self, column: Column) -> Result<Position, MoveError> {
+        let tiles = self.tiles.get(column.x()).ok_or(MoveError::OutOfBounds)?;
+        let y = tiles
+            .iter()
+            .rposition(Tile::is_unplayed)
+            .ok_or(MoveError::ColumnFull)?;
+        // the board size is limited, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(Position::new(column.0, y as u8))
+    }
+
+    /// checks the position can be played when pieces fall: it must be where a piece dropped in
+    /// its column lands
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_drop(&self, pos: Position) -> Result<(), MoveError> {
+        self.check_move(pos)?;
+        if self.landing(pos.into())? != pos {
+            return Err(MoveError::Floating);
+        }
+        Ok(())
+    }
+
+    /// every position a piece can land
+    pub fn landings(&self) -> Vec<Position> {
+        if self.summary().is_finished() {
+            return Vec::new();
+        }
+        // the board size is limited, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        (0..self.width())
+            .filter_map(|x| self.landing(Column(x as u8)).ok())
+            .collect()
+    }
+
+    /// plays the tile at the position, which must be where a piece dropped in its column lands
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn play_drop(&mut self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        self.check_drop(pos)?;
+        self.play(pos, tile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoardSize;
+
+    #[test]
+    fn test_pieces_fall() {
+        let mut board = Board::with_size(BoardSize::new(7, 6, 4).unwrap());
+        let bottom = board.landing(Column(3)).unwrap();
+        assert_eq!(bottom, (3, 5).try_into().unwrap());
+        board.play_drop(bottom, Tile::Cross).unwrap();
+        assert_eq!(board.landing(Column(3)), Ok((3, 4).try_into().unwrap()));
+        assert_eq!(
+            board.check_drop((3, 0).try_into().unwrap()),
+            Err(MoveError::Floating)
+        );
+        assert_eq!(board.landings().len(), 7);
+        assert_eq!(board.landing(Column(7)), Err(MoveError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_full_column() {
+        let board: Board = "X../O../X..".parse().unwrap();
+        assert_eq!(board.landing(Column(0)), Err(MoveError::ColumnFull));
+        assert_eq!(
+            board.landings(),
+            [(1, 2), (2, 2)].map(|xy| Position::try_from(xy).unwrap())
+        );
+    }
+}
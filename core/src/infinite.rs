@@ -0,0 +1,121 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::Position, Board, GameSummary, MoveError, Tile};
+
+/// a 3x3 board where each symbol can only have three pieces. Placing a fourth removes the oldest,
+/// so the board never fills up and the game can't be tied.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct InfiniteBoard {
+    pub board: Board,
+    /// every piece on the board, oldest first
+    pub queue: VecDeque<Position>,
+}
+
+impl InfiniteBoard {
+    /// the most pieces of each symbol on the board
+    pub const PIECES: usize = 3;
+
+    pub fn clear(&mut self) {
+        self.board.clear();
+        self.queue.clear();
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.board.summary()
+    }
+
+    /// the pieces of the symbol, oldest first
+    pub fn pieces(&self, tile: Tile) -> impl Iterator<Item = Position> + '_ {
+        self.queue
+            .iter()
+            .copied()
+            .filter(move |&pos| self.board.get(pos) == Some(tile))
+    }
+
+    /// the piece removed when `tile` is next placed, if it already has all its pieces on the
+    /// board
+    pub fn vanishing(&self, tile: Tile) -> Option<Position> {
+        if self.pieces(tile).count() < Self::PIECES {
+            return None;
+        }
+        self.pieces(tile).next()
+    }
+
+    /// places the tile, removing its oldest piece if it already has three
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn play(&mut self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        self.board.check_move(pos)?;
+        if let Some(oldest) = self.vanishing(tile) {
+            self.board.tiles[oldest.x()][oldest.y()] = Tile::Unplayed;
+            self.queue.retain(|&p| p != oldest);
+        }
+        self.board.play(pos, tile)?;
+        self.queue.push_back(pos);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pos;
+
+    #[test]
+    fn test_oldest_piece_vanishes() {
+        let mut board = InfiniteBoard::default();
+        for (xy, tile) in [
+            ((0, 0), Tile::Cross),
+            ((1, 1), Tile::Nought),
+            ((2, 0), Tile::Cross),
+            ((1, 0), Tile::Nought),
+            ((0, 2), Tile::Cross),
+        ] {
+            board.play(pos(xy.0, xy.1), tile).unwrap();
+        }
+        assert_eq!(board.vanishing(Tile::Nought), None);
+        assert_eq!(board.vanishing(Tile::Cross), Some(pos(0, 0)));
+
+        board.play(pos(2, 1), Tile::Nought).unwrap();
+        assert_eq!(board.vanishing(Tile::Nought), Some(pos(1, 1)));
+        board.play(pos(2, 2), Tile::Cross).unwrap();
+        assert_eq!(board.board.get(pos(0, 0)), Some(Tile::Unplayed));
+        assert_eq!(board.pieces(Tile::Cross).count(), 3);
+        assert_eq!(board.queue.len(), 6);
+        assert_eq!(board.summary(), GameSummary::InProgress);
+    }
+
+    #[test]
+    fn test_line_wins() {
+        let mut board = InfiniteBoard::default();
+        for (xy, tile) in [
+            ((0, 0), Tile::Cross),
+            ((1, 1), Tile::Nought),
+            ((0, 1), Tile::Cross),
+            ((2, 2), Tile::Nought),
+            ((0, 2), Tile::Cross),
+        ] {
+            board.play(pos(xy.0, xy.1), tile).unwrap();
+        }
+        assert_eq!(board.summary(), GameSummary::CrossWin);
+        assert_eq!(
+            board.play(pos(2, 0), Tile::Nought),
+            Err(MoveError::GameOver)
+        );
+    }
+
+    #[test]
+    fn test_occupied() {
+        let mut board = InfiniteBoard::default();
+        board.play(pos(1, 1), Tile::Cross).unwrap();
+        assert_eq!(
+            board.play(pos(1, 1), Tile::Nought),
+            Err(MoveError::Occupied)
+        );
+        assert_eq!(board.queue, [pos(1, 1)]);
+    }
+}
@@ -1,12 +1,38 @@
 #![warn(clippy::all, clippy::nursery, clippy::pedantic)]
 #![allow(clippy::must_use_candidate)]
 
+use core::fmt;
+
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::game_state::Position;
 
+pub mod bitboard;
+pub mod bot;
+pub mod game;
 pub mod game_state;
+pub mod gobblet;
+pub mod gomoku;
+pub mod gravity;
+pub mod infinite;
 pub mod messages;
+pub mod notakto;
+pub mod notation;
+pub mod numerical;
+pub mod options;
+pub mod order_chaos;
+pub mod quantum;
+pub mod qubic;
+pub mod record;
+pub mod solver;
+pub mod symmetry;
+#[cfg(test)]
+mod testing;
+pub mod ultimate;
+pub mod validate;
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Tile {
     #[default]
     Unplayed,
@@ -24,6 +50,15 @@ impl Tile {
     pub fn is_unplayed(&self) -> bool {
         *self == Self::Unplayed
     }
+    /// the tile of the other player. Unplayed has no opponent.
+    #[must_use]
+    pub const fn opponent(&self) -> Self {
+        match self {
+            Self::Unplayed => Self::Unplayed,
+            Self::Nought => Self::Cross,
+            Self::Cross => Self::Nought,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -39,18 +74,335 @@ impl GameSummary {
     pub fn is_finished(&self) -> bool {
         self.ne(&Self::InProgress)
     }
+    /// the tile that won, if any
+    pub const fn winner(&self) -> Option<Tile> {
+        match self {
+            Self::NoughtWin => Some(Tile::Nought),
+            Self::CrossWin => Some(Tile::Cross),
+            Self::InProgress | Self::Tie => None,
+        }
+    }
 }
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Error, Debug)]
+pub enum RulesParseError {
+    #[error("must be one of normal, misere or wild")]
+    Unknown,
+}
+
+/// how completing a line decides the game
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Rules {
+    /// completing a line wins
+    #[default]
+    Normal,
+    /// completing a line loses
+    Misere,
+    /// either player may place either symbol, and whoever completes a line wins
+    Wild,
+}
+
+impl TryFrom<&str> for Rules {
+    type Error = RulesParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "normal" => Ok(Self::Normal),
+            "misere" => Ok(Self::Misere),
+            "wild" => Ok(Self::Wild),
+            _ => Err(Self::Error::Unknown),
+        }
+    }
+}
+
+impl Rules {
+    /// the result of the game, given the summary of whose line was completed and the player who
+    /// moved last, if anyone has moved
+    pub const fn apply(self, summary: GameSummary, mover: Option<Tile>) -> GameSummary {
+        match (self, summary, mover) {
+            (Self::Misere, GameSummary::CrossWin, _) => GameSummary::NoughtWin,
+            (Self::Misere, GameSummary::NoughtWin, _) => GameSummary::CrossWin,
+            (Self::Wild, GameSummary::CrossWin | GameSummary::NoughtWin, Some(Tile::Cross)) => {
+                GameSummary::CrossWin
+            }
+            (Self::Wild, GameSummary::CrossWin | GameSummary::NoughtWin, Some(Tile::Nought)) => {
+                GameSummary::NoughtWin
+            }
+            (_, summary, _) => summary,
+        }
+    }
+
+    /// the symbols `player` may place
+    pub const fn symbols(self, player: Tile) -> &'static [Tile] {
+        match (self, player) {
+            (Self::Wild, _) => &[Tile::Cross, Tile::Nought],
+            (_, Tile::Cross) => &[Tile::Cross],
+            (_, Tile::Nought) => &[Tile::Nought],
+            (_, Tile::Unplayed) => &[],
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum BoardSizeError {
+    #[error("must be written as width,height,win length")]
+    InvalidFormat,
+    #[error("width and height must be between 1 and 16")]
+    OutOfBounds,
+    #[error("win length must be between 1 and the longest side")]
+    InvalidWinLength,
+}
+
+/// why a move can't be played
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    #[error("the game is over")]
+    GameOver,
+    #[error("that position is not on the board")]
+    OutOfBounds,
+    #[error("that tile has already been played")]
+    Occupied,
+    #[error("you must play in the highlighted board")]
+    WrongBoard,
+    #[error("a move must play a nought or a cross")]
+    Unplayed,
+    #[error("you can only play your own symbol")]
+    WrongSymbol,
+    #[error("that board already has a line")]
+    DeadBoard,
+    #[error("the first player may not make an overline or a double three")]
+    Forbidden,
+    #[error("that column is full")]
+    ColumnFull,
+    #[error("pieces fall to the bottom of the column")]
+    Floating,
+    #[error("pieces can only be dropped in gravity games")]
+    NoGravity,
+    #[error("quantum moves are played in two cells")]
+    Paired,
+    #[error("a quantum move needs two different cells, unless only one is left")]
+    SameCell,
+    #[error("the last move made a cycle, which must be collapsed first")]
+    Uncollapsed,
+    #[error("there is no cycle to collapse")]
+    NothingToCollapse,
+    #[error("the last move wasn't played there")]
+    NotEntangled,
+    #[error("only quantum games have paired moves")]
+    NotQuantum,
+    #[error("gobblet moves say which size of piece to play")]
+    NoSize,
+    #[error("a piece can only cover a smaller one")]
+    TooSmall,
+    #[error("you have no pieces of that size left")]
+    NoPiece,
+    #[error("you can only move your own piece from the top of a stack")]
+    NotYourPiece,
+    #[error("only gobblet games have pieces of different sizes")]
+    NotGobblet,
+    #[error("numerical moves say which number to play")]
+    NoNumber,
+    #[error("the numbers run from 1 to 9")]
+    NotANumber,
+    #[error("you can only play your own numbers")]
+    WrongParity,
+    #[error("that number has already been played")]
+    NumberUsed,
+    #[error("only numerical games are played with numbers")]
+    NotNumerical,
+}
+
+/// the dimensions of a board and how many in a row are needed to win, as in an m,n,k-game
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct BoardSize {
+    width: usize,
+    height: usize,
+    win_length: usize,
+}
+
+impl Default for BoardSize {
+    fn default() -> Self {
+        Self {
+            width: 3,
+            height: 3,
+            win_length: 3,
+        }
+    }
+}
+
+impl BoardSize {
+    /// the largest width or height a board can have
+    pub const MAX: usize = Position::MAX;
+
+    /// # Errors
+    /// if a side is empty or longer than [`Self::MAX`], or the win length can't fit on the board
+    pub const fn new(
+        width: usize,
+        height: usize,
+        win_length: usize,
+    ) -> Result<Self, BoardSizeError> {
+        if width == 0 || height == 0 || width > Self::MAX || height > Self::MAX {
+            Err(BoardSizeError::OutOfBounds)
+        } else if win_length == 0 || (win_length > width && win_length > height) {
+            Err(BoardSizeError::InvalidWinLength)
+        } else {
+            Ok(Self {
+                width,
+                height,
+                win_length,
+            })
+        }
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+    pub const fn win_length(&self) -> usize {
+        self.win_length
+    }
+}
+
+impl TryFrom<&str> for BoardSize {
+    type Error = BoardSizeError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let dimensions = value
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<usize>, _>>()
+            .map_err(|_| BoardSizeError::InvalidFormat)?;
+
+        match dimensions[..] {
+            [width, height, win_length] => Self::new(width, height, win_length),
+            _ => Err(BoardSizeError::InvalidFormat),
+        }
+    }
+}
+
+impl fmt::Display for BoardSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.width, self.height, self.win_length)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Board {
-    pub tiles: [[Tile; 3]; 3],
+    /// indexed by x then y
+    pub tiles: Vec<Vec<Tile>>,
+    /// how many in a row are needed to win
+    pub win_length: usize,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::with_size(BoardSize::default())
+    }
+}
+
+impl From<[[Tile; 3]; 3]> for Board {
+    fn from(tiles: [[Tile; 3]; 3]) -> Self {
+        Self {
+            tiles: tiles.iter().map(|row| row.to_vec()).collect(),
+            win_length: 3,
+        }
+    }
 }
 
 impl Board {
+    /// the directions a line can run in
+    const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+    /// the rows, columns and diagonals of the 3x3 board
+    const LINES: [[Position; 3]; 8] = [
+        [
+            Position::new(0, 0),
+            Position::new(1, 0),
+            Position::new(2, 0),
+        ],
+        [
+            Position::new(0, 1),
+            Position::new(1, 1),
+            Position::new(2, 1),
+        ],
+        [
+            Position::new(0, 2),
+            Position::new(1, 2),
+            Position::new(2, 2),
+        ],
+        [
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(0, 2),
+        ],
+        [
+            Position::new(1, 0),
+            Position::new(1, 1),
+            Position::new(1, 2),
+        ],
+        [
+            Position::new(2, 0),
+            Position::new(2, 1),
+            Position::new(2, 2),
+        ],
+        [
+            Position::new(0, 0),
+            Position::new(1, 1),
+            Position::new(2, 2),
+        ],
+        [
+            Position::new(2, 0),
+            Position::new(1, 1),
+            Position::new(0, 2),
+        ],
+    ];
+
+    /// a classic 3x3 board
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// an empty board of the given size
+    pub fn with_size(size: BoardSize) -> Self {
+        Self {
+            tiles: vec![vec![Tile::Unplayed; size.height]; size.width],
+            win_length: size.win_length,
+        }
+    }
+
+    pub const fn width(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.tiles.first().map_or(0, Vec::len)
+    }
+
+    pub fn size(&self) -> BoardSize {
+        BoardSize {
+            width: self.width(),
+            height: self.height(),
+            win_length: self.win_length,
+        }
+    }
+
+    /// every position on the board
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let height = self.height();
+        (0..self.width()).flat_map(move |x| {
+            // the board size is limited, so truncation can't happen
+            #[allow(clippy::cast_possible_truncation)]
+            (0..height).map(move |y| Position::new(x as u8, y as u8))
+        })
+    }
+
+    /// the tile at the position, if it is on the board
+    pub fn get(&self, pos: Position) -> Option<Tile> {
+        self.tiles.get(pos.x())?.get(pos.y()).copied()
+    }
+
     pub fn clear(&mut self) {
         for row in &mut self.tiles {
             for tile in row.iter_mut() {
@@ -59,33 +411,69 @@ impl Board {
         }
     }
 
-    pub fn summary(&self) -> GameSummary {
-        // check all the rows
-        if self.tiles.iter().any(|row| row.iter().all(Tile::is_cross)) {
-            return GameSummary::CrossWin;
-        }
-        if self.tiles.iter().any(|row| row.iter().all(Tile::is_nought)) {
-            return GameSummary::NoughtWin;
-        }
-        // check all the columns
-        if (0..3).any(|col| self.tiles.iter().all(|row| row[col].is_cross())) {
-            return GameSummary::CrossWin;
+    /// checks the position can be played
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the game is over, or the position is off the board or already played.
+    pub fn check_move(&self, pos: Position) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
         }
-        if (0..3).any(|col| self.tiles.iter().all(|row| row[col].is_nought())) {
-            return GameSummary::NoughtWin;
+        match self.get(pos) {
+            None => Err(MoveError::OutOfBounds),
+            Some(Tile::Unplayed) => Ok(()),
+            Some(_) => Err(MoveError::Occupied),
         }
-        // check diagonals
-        if (0..3).all(|i| self.tiles[i][i].is_cross()) {
-            return GameSummary::CrossWin;
-        }
-        if (0..3).all(|i| self.tiles[i][i].is_nought()) {
-            return GameSummary::NoughtWin;
+    }
+
+    /// every position that can be played
+    pub fn legal_moves(&self) -> Vec<Position> {
+        if self.summary().is_finished() {
+            return Vec::new();
         }
-        if (0..3).all(|i| self.tiles[i][2 - i].is_cross()) {
-            return GameSummary::CrossWin;
+        self.positions()
+            .filter(|&pos| self.get(pos) == Some(Tile::Unplayed))
+            .collect()
+    }
+
+    /// plays the tile at the position
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn play(&mut self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        if tile.is_unplayed() {
+            return Err(MoveError::Unplayed);
         }
-        if (0..3).all(|i| self.tiles[i][2 - i].is_nought()) {
-            return GameSummary::NoughtWin;
+        self.check_move(pos)?;
+        self.tiles[pos.x()][pos.y()] = tile;
+        Ok(())
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary, and the tiles that won the game if it has been won
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        // check every line starting from every tile
+        for start in self.positions() {
+            let tile = self.get(start).unwrap_or_default();
+            if tile.is_unplayed() {
+                continue;
+            }
+
+            for direction in Self::DIRECTIONS {
+                if let Some(line) = self.line(start, direction, tile) {
+                    let summary = if tile.is_cross() {
+                        GameSummary::CrossWin
+                    } else {
+                        GameSummary::NoughtWin
+                    };
+                    return (summary, Some(line));
+                }
+            }
         }
         // if there are no more moves
         if self
@@ -93,10 +481,22 @@ impl Board {
             .iter()
             .all(|row| row.iter().all(|t| !t.is_unplayed()))
         {
-            return GameSummary::Tie;
+            return (GameSummary::Tie, None);
         }
 
-        GameSummary::InProgress
+        (GameSummary::InProgress, None)
+    }
+
+    /// the winning line of `tile` from the start in the direction, if there is one
+    fn line(&self, start: Position, (dx, dy): (isize, isize), tile: Tile) -> Option<Vec<Position>> {
+        (0..self.win_length)
+            .map(|i| {
+                let x = start.x().checked_add_signed(dx * i.cast_signed())?;
+                let y = start.y().checked_add_signed(dy * i.cast_signed())?;
+                let pos = Position::try_from((x, y)).ok()?;
+                (self.get(pos)? == tile).then_some(pos)
+            })
+            .collect()
     }
 }
 
@@ -104,13 +504,14 @@ impl Board {
 mod tests {
     use super::*;
 
+    fn positions(positions: &[(usize, usize)]) -> Vec<Position> {
+        positions.iter().map(|&p| p.try_into().unwrap()).collect()
+    }
+
     #[test]
     fn test_cross_win_row_0() {
         assert!(
-            Board {
-                tiles: [[Tile::Cross; 3], [Tile::Unplayed; 3], [Tile::Unplayed; 3],]
-            }
-            .summary()
+            Board::from([[Tile::Cross; 3], [Tile::Unplayed; 3], [Tile::Unplayed; 3],]).summary()
                 == GameSummary::CrossWin
         )
     }
@@ -118,10 +519,7 @@ mod tests {
     #[test]
     fn test_nought_win_row_2() {
         assert!(
-            Board {
-                tiles: [[Tile::Unplayed; 3], [Tile::Unplayed; 3], [Tile::Nought; 3],]
-            }
-            .summary()
+            Board::from([[Tile::Unplayed; 3], [Tile::Unplayed; 3], [Tile::Nought; 3],]).summary()
                 == GameSummary::NoughtWin
         )
     }
@@ -129,13 +527,11 @@ mod tests {
     #[test]
     fn test_cross_win_col_0() {
         assert!(
-            Board {
-                tiles: [
-                    [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
-                    [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
-                    [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
-                ]
-            }
+            Board::from([
+                [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
+                [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
+                [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
+            ])
             .summary()
                 == GameSummary::CrossWin
         )
@@ -144,13 +540,11 @@ mod tests {
     #[test]
     fn test_cross_win_diagonal() {
         assert!(
-            Board {
-                tiles: [
-                    [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
-                    [Tile::Unplayed, Tile::Cross, Tile::Unplayed],
-                    [Tile::Unplayed, Tile::Unplayed, Tile::Cross],
-                ]
-            }
+            Board::from([
+                [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
+                [Tile::Unplayed, Tile::Cross, Tile::Unplayed],
+                [Tile::Unplayed, Tile::Unplayed, Tile::Cross],
+            ])
             .summary()
                 == GameSummary::CrossWin
         )
@@ -159,13 +553,11 @@ mod tests {
     #[test]
     fn test_nought_win_reverse_diagonal() {
         assert!(
-            Board {
-                tiles: [
-                    [Tile::Cross, Tile::Cross, Tile::Nought],
-                    [Tile::Cross, Tile::Nought, Tile::Cross],
-                    [Tile::Nought, Tile::Cross, Tile::Cross],
-                ]
-            }
+            Board::from([
+                [Tile::Cross, Tile::Cross, Tile::Nought],
+                [Tile::Cross, Tile::Nought, Tile::Cross],
+                [Tile::Nought, Tile::Cross, Tile::Cross],
+            ])
             .summary()
                 == GameSummary::NoughtWin
         )
@@ -174,13 +566,11 @@ mod tests {
     #[test]
     fn test_empty_in_progress() {
         assert!(
-            Board {
-                tiles: [
-                    [Tile::Unplayed; 3],
-                    [Tile::Unplayed; 3],
-                    [Tile::Unplayed; 3],
-                ]
-            }
+            Board::from([
+                [Tile::Unplayed; 3],
+                [Tile::Unplayed; 3],
+                [Tile::Unplayed; 3],
+            ])
             .summary()
                 == GameSummary::InProgress
         )
@@ -189,15 +579,157 @@ mod tests {
     #[test]
     fn test_full_tie() {
         assert!(
-            Board {
-                tiles: [
-                    [Tile::Nought, Tile::Cross, Tile::Nought],
-                    [Tile::Nought, Tile::Cross, Tile::Nought],
-                    [Tile::Cross, Tile::Nought, Tile::Cross],
-                ]
-            }
+            Board::from([
+                [Tile::Nought, Tile::Cross, Tile::Nought],
+                [Tile::Nought, Tile::Cross, Tile::Nought],
+                [Tile::Cross, Tile::Nought, Tile::Cross],
+            ])
             .summary()
                 == GameSummary::Tie
         )
     }
+
+    #[test]
+    fn test_winning_line_diagonal() {
+        let (summary, line) = Board::from([
+            [Tile::Cross, Tile::Nought, Tile::Unplayed],
+            [Tile::Nought, Tile::Cross, Tile::Unplayed],
+            [Tile::Unplayed, Tile::Unplayed, Tile::Cross],
+        ])
+        .summary_with_line();
+        assert_eq!(summary, GameSummary::CrossWin);
+        assert_eq!(line, Some(positions(&[(0, 0), (1, 1), (2, 2)])));
+    }
+
+    #[test]
+    fn test_no_winning_line_for_tie() {
+        let (summary, line) = Board::from([
+            [Tile::Nought, Tile::Cross, Tile::Nought],
+            [Tile::Nought, Tile::Cross, Tile::Nought],
+            [Tile::Cross, Tile::Nought, Tile::Cross],
+        ])
+        .summary_with_line();
+        assert_eq!(summary, GameSummary::Tie);
+        assert_eq!(line, None);
+    }
+
+    #[test]
+    fn test_play() {
+        let mut board = Board::new();
+        let centre = (1, 1).try_into().unwrap();
+        assert_eq!(board.play(centre, Tile::Cross), Ok(()));
+        assert_eq!(board.get(centre), Some(Tile::Cross));
+        assert_eq!(board.play(centre, Tile::Nought), Err(MoveError::Occupied));
+        assert_eq!(
+            board.play((3, 0).try_into().unwrap(), Tile::Nought),
+            Err(MoveError::OutOfBounds)
+        );
+        assert_eq!(
+            board.play((0, 0).try_into().unwrap(), Tile::Unplayed),
+            Err(MoveError::Unplayed)
+        );
+        assert_eq!(board.legal_moves().len(), 8);
+    }
+
+    #[test]
+    fn test_no_moves_after_win() {
+        let mut board = Board::from([
+            [Tile::Cross, Tile::Cross, Tile::Cross],
+            [Tile::Nought, Tile::Nought, Tile::Unplayed],
+            [Tile::Unplayed; 3],
+        ]);
+        assert_eq!(board.legal_moves(), []);
+        assert_eq!(
+            board.play((2, 2).try_into().unwrap(), Tile::Nought),
+            Err(MoveError::GameOver)
+        );
+    }
+
+    #[test]
+    fn test_misere_completing_a_line_loses() {
+        let board: Board = "XO./XO./X..".parse().unwrap();
+        let mover = Some(Tile::Cross);
+        assert_eq!(
+            Rules::Normal.apply(board.summary(), mover),
+            GameSummary::CrossWin
+        );
+        assert_eq!(
+            Rules::Misere.apply(board.summary(), mover),
+            GameSummary::NoughtWin
+        );
+        assert_eq!(
+            Rules::Misere.apply(GameSummary::Tie, mover),
+            GameSummary::Tie
+        );
+        // in wild games the line belongs to whoever completed it
+        assert_eq!(
+            Rules::Wild.apply(board.summary(), Some(Tile::Nought)),
+            GameSummary::NoughtWin
+        );
+    }
+
+    #[test]
+    fn test_board_size_parse() {
+        assert_eq!(
+            BoardSize::try_from("15,15,5").unwrap(),
+            BoardSize::new(15, 15, 5).unwrap()
+        );
+        assert!(BoardSize::try_from("4,4").is_err());
+        assert!(BoardSize::try_from("17,3,3").is_err());
+        assert!(BoardSize::try_from("3,3,4").is_err());
+    }
+
+    #[test]
+    fn test_four_in_a_row_needs_four() {
+        let mut board = Board::with_size(BoardSize::new(4, 4, 4).unwrap());
+        for y in 0..3 {
+            board.tiles[1][y] = Tile::Cross;
+        }
+        assert_eq!(board.summary(), GameSummary::InProgress);
+
+        board.tiles[1][3] = Tile::Cross;
+        assert_eq!(
+            board.summary_with_line(),
+            (
+                GameSummary::CrossWin,
+                Some(positions(&[(1, 0), (1, 1), (1, 2), (1, 3)]))
+            )
+        );
+    }
+
+    #[test]
+    fn test_gomoku_reverse_diagonal() {
+        let mut board = Board::with_size(BoardSize::new(15, 15, 5).unwrap());
+        for i in 0..5 {
+            board.tiles[10 + i][14 - i] = Tile::Nought;
+        }
+        assert_eq!(
+            board.summary_with_line(),
+            (
+                GameSummary::NoughtWin,
+                Some(positions(&[
+                    (10, 14),
+                    (11, 13),
+                    (12, 12),
+                    (13, 11),
+                    (14, 10)
+                ]))
+            )
+        );
+    }
+
+    #[test]
+    fn test_rectangular_board_tie() {
+        let mut board = Board::with_size(BoardSize::new(2, 4, 3).unwrap());
+        for (x, row) in board.tiles.iter_mut().enumerate() {
+            for (y, tile) in row.iter_mut().enumerate() {
+                *tile = if (x + y / 2) % 2 == 0 {
+                    Tile::Cross
+                } else {
+                    Tile::Nought
+                };
+            }
+        }
+        assert_eq!(board.summary(), GameSummary::Tie);
+    }
 }
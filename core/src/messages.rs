@@ -1,24 +1,70 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{game_state::Position, Board};
+use crate::{
+    bot::Difficulty,
+    game::Game,
+    game_state::{Position, SeatToken},
+    gobblet::GobbletMove,
+    gravity::Column,
+    Tile,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ServerMessage {
     Error(String),
+    Seated(SeatAssignment),
+    /// the game is full. Updates are still sent, but moves are rejected.
+    Spectating,
     WaitingForOpponentYouAreCrosses,
     GameUpdate(GameUpdate),
     OppositionRequestsRematch,
+    OpponentDisconnected,
     GameEnded,
 }
 
+/// sent when a player takes or reclaims a seat
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SeatAssignment {
+    /// present this in [`ClientMessage::Reclaim`] to take the seat back after reconnecting
+    pub token: SeatToken,
+    pub crosses: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct GameUpdate {
-    pub board: Board,
+    pub game: Game,
+    /// whether it is the receiving player's turn. Always false for spectators.
     pub turn: bool,
+    pub crosses_turn: bool,
+    /// the tiles that won the game
+    pub winning_line: Option<Vec<Position>>,
+    /// the piece removed when the player to move next moves, in infinite games
+    #[serde(default)]
+    pub vanishing: Option<Position>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ClientMessage {
+    /// take the next free seat
+    Join,
+    /// take back a seat after reconnecting
+    Reclaim(SeatToken),
+    /// fill the empty noughts seat with a computer opponent
+    PlayComputer(Difficulty),
+    /// play your own symbol at the position
     Move(Position),
+    /// place a symbol at the position. Only wild games let a player place the other symbol.
+    Place(Position, Tile),
+    /// drop your own symbol in the column, in gravity games
+    Drop(Column),
+    /// place spooky marks of your own symbol in both cells, in quantum games
+    Entangle(Position, Position),
+    /// choose where the cycle your opponent made collapses, in quantum games. It is still your
+    /// turn afterwards.
+    Collapse(Position),
+    /// play a new piece of your own, or move one from the top of a stack, in gobblet games
+    Gobble(GobbletMove),
+    /// play one of your numbers at the position, in numerical games
+    Number(Position, u8),
     RequestRematch,
 }
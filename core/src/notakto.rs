@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::Position, Board, GameSummary, MoveError, Tile};
+
+/// several boards where both players play crosses. A board dies once it has a line, and whoever
+/// kills the last board loses.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct NotaktoBoard {
+    /// laid out side by side from left to right
+    pub boards: Vec<Board>,
+    /// the board the last cross was played on. Once every board is dead, it is the one that ended
+    /// the game.
+    #[serde(default)]
+    pub last_played: Option<usize>,
+}
+
+impl Default for NotaktoBoard {
+    fn default() -> Self {
+        Self::new(Self::BOARDS)
+    }
+}
+
+impl NotaktoBoard {
+    /// the number of boards in a new game
+    pub const BOARDS: usize = 3;
+    /// the width and height of each board
+    pub const SIZE: usize = 3;
+
+    /// `count` empty 3x3 boards
+    pub fn new(count: usize) -> Self {
+        Self {
+            boards: vec![Board::new(); count],
+            last_played: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for board in &mut self.boards {
+            board.clear();
+        }
+        self.last_played = None;
+    }
+
+    /// the index of the board and the position within it
+    pub const fn split(pos: Position) -> (usize, Position) {
+        // positions within a board are less than 3, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        (
+            pos.x() / Self::SIZE,
+            Position::new((pos.x() % Self::SIZE) as u8, pos.y() as u8),
+        )
+    }
+
+    /// whether the board has a line, so can't be played any more
+    pub fn is_dead(board: &Board) -> bool {
+        board.summary().winner().is_some()
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary, and the line that killed the last board. The game is won by crosses once every
+    /// board is dead, since crosses completed every line; [`Game`](crate::game::Game) works out
+    /// who that was.
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        if !self.boards.iter().all(Self::is_dead) {
+            return (GameSummary::InProgress, None);
+        }
+
+        let line = self.last_played.and_then(|i| {
+            let (_, line) = self.boards.get(i)?.summary_with_line();
+            // boards are small, so truncation can't happen
+            #[allow(clippy::cast_possible_truncation)]
+            line.map(|line| {
+                line.into_iter()
+                    .map(|pos| Position::new((i * Self::SIZE + pos.x()) as u8, pos.y() as u8))
+                    .collect()
+            })
+        });
+        (GameSummary::CrossWin, line)
+    }
+
+    /// every board laid out side by side on a single board
+    pub fn tiles(&self) -> Board {
+        let mut tiles = Board {
+            tiles: vec![vec![Tile::Unplayed; Self::SIZE]; Self::SIZE * self.boards.len()],
+            win_length: 3,
+        };
+        for pos in tiles.clone().positions() {
+            let (i, inner) = Self::split(pos);
+            tiles.tiles[pos.x()][pos.y()] = self.boards[i].get(inner).unwrap_or_default();
+        }
+        tiles
+    }
+
+    /// checks the position can be played: the game isn't over, and the tile is unplayed on a board
+    /// that is still alive
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_move(&self, pos: Position) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
+        }
+        let (i, inner) = Self::split(pos);
+        let board = self.boards.get(i).ok_or(MoveError::OutOfBounds)?;
+        board.check_move(inner).map_err(|e| match e {
+            MoveError::GameOver => MoveError::DeadBoard,
+            e => e,
+        })
+    }
+
+    pub fn is_legal(&self, pos: Position) -> bool {
+        self.check_move(pos).is_ok()
+    }
+
+    /// every position that can be played
+    pub fn legal_moves(&self) -> Vec<Position> {
+        self.tiles()
+            .positions()
+            .filter(|&pos| self.is_legal(pos))
+            .collect()
+    }
+
+    /// plays a cross at the position
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal or the tile isn't a cross. The board is left
+    /// unchanged.
+    pub fn play(&mut self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        if tile.is_nought() {
+            return Err(MoveError::WrongSymbol);
+        }
+        self.check_move(pos)?;
+        let (i, inner) = Self::split(pos);
+        self.boards[i].play(inner, tile)?;
+        self.last_played = Some(i);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pos;
+
+    #[test]
+    fn test_dead_board() {
+        let mut board = NotaktoBoard::new(2);
+        for y in 0..3 {
+            board.play(pos(4, y), Tile::Cross).unwrap();
+        }
+        assert_eq!(board.check_move(pos(3, 0)), Err(MoveError::DeadBoard));
+        assert!(board.is_legal(pos(0, 0)));
+        assert_eq!(board.legal_moves().len(), 9);
+
+        // killing a board doesn't end the game, so there is no line to show yet
+        assert_eq!(board.summary_with_line(), (GameSummary::InProgress, None));
+    }
+
+    #[test]
+    fn test_every_board_dead() {
+        let mut board = NotaktoBoard::new(2);
+        for x in [0, 3] {
+            for y in 0..3 {
+                board.play(pos(x, y), Tile::Cross).unwrap();
+            }
+        }
+        assert_eq!(
+            board.summary_with_line(),
+            (
+                GameSummary::CrossWin,
+                Some(vec![pos(3, 0), pos(3, 1), pos(3, 2)])
+            )
+        );
+        assert_eq!(board.check_move(pos(1, 1)), Err(MoveError::GameOver));
+        assert_eq!(board.legal_moves(), []);
+    }
+
+    #[test]
+    fn test_crosses_only() {
+        let mut board = NotaktoBoard::default();
+        assert_eq!(
+            board.play(pos(0, 0), Tile::Nought),
+            Err(MoveError::WrongSymbol)
+        );
+        assert_eq!(board.check_move(pos(9, 0)), Err(MoveError::OutOfBounds));
+        assert_eq!(board.tiles().width(), 9);
+    }
+}
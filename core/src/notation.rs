@@ -0,0 +1,252 @@
+//! compact text for boards and moves, for logs, tests and bug reports.
+//!
+//! A board is written row by row from `y = 0`, with rows separated by `/`, crosses as `X`, noughts
+//! as `O` and unplayed tiles as `.`, such as `X.O/.X./..O`. The win length follows a `:` when it
+//! isn't the usual three, such as `..../..../..../....:4`.
+//!
+//! A position is written as a column letter and a row number, such as `b3` for `x = 1, y = 2`. A
+//! list of moves is written as positions separated by spaces.
+
+use core::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{
+    game_state::{Position, PositionParseError},
+    Board, BoardSize, BoardSizeError, Tile,
+};
+
+#[derive(Error, Debug)]
+pub enum BoardParseError {
+    #[error("unknown tile {0:?}, expected X, O or .")]
+    InvalidTile(char),
+    #[error("every row must be the same length")]
+    UnevenRows,
+    #[error("invalid win length")]
+    InvalidWinLength,
+    #[error("invalid board size: {0}")]
+    Size(#[from] BoardSizeError),
+}
+
+#[derive(Error, Debug)]
+pub enum MoveParseError {
+    #[error("{0:?} must be a column letter followed by a row number")]
+    InvalidFormat(String),
+    #[error("{0}")]
+    Position(#[from] PositionParseError),
+}
+
+impl Tile {
+    const fn symbol(self) -> char {
+        match self {
+            Self::Unplayed => '.',
+            Self::Nought => 'O',
+            Self::Cross => 'X',
+        }
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = BoardParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Unplayed),
+            'O' | 'o' => Ok(Self::Nought),
+            'X' | 'x' => Ok(Self::Cross),
+            c => Err(Self::Error::InvalidTile(c)),
+        }
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl Board {
+    /// the win length assumed when none is written
+    const fn default_win_length(width: usize, height: usize) -> usize {
+        let longest = if width > height { width } else { height };
+        if longest < 3 {
+            longest
+        } else {
+            3
+        }
+    }
+}
+
+impl FromStr for Board {
+    type Err = BoardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rows, win_length) = match s.trim().split_once(':') {
+            Some((rows, k)) => (
+                rows,
+                Some(k.parse().map_err(|_| BoardParseError::InvalidWinLength)?),
+            ),
+            None => (s.trim(), None),
+        };
+
+        let rows = rows
+            .split('/')
+            .map(|row| row.chars().map(Tile::try_from).collect())
+            .collect::<Result<Vec<Vec<Tile>>, _>>()?;
+
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(BoardParseError::UnevenRows);
+        }
+        let height = rows.len();
+        let win_length = win_length.unwrap_or_else(|| Self::default_win_length(width, height));
+
+        let mut board = Self::with_size(BoardSize::new(width, height, win_length)?);
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, tile) in row.into_iter().enumerate() {
+                board.tiles[x][y] = tile;
+            }
+        }
+        Ok(board)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height() {
+            if y > 0 {
+                write!(f, "/")?;
+            }
+            for x in 0..self.width() {
+                write!(f, "{}", self.tiles[x][y])?;
+            }
+        }
+        if self.win_length != Self::default_win_length(self.width(), self.height()) {
+            write!(f, ":{}", self.win_length)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Position {
+    type Err = MoveParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || MoveParseError::InvalidFormat(s.to_string());
+
+        let mut chars = s.chars();
+        let column = chars
+            .next()
+            .filter(char::is_ascii_lowercase)
+            .ok_or_else(invalid)?;
+        let row: usize = chars.as_str().parse().map_err(|_| invalid())?;
+        if row == 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self::try_from((column as usize - 'a' as usize, row - 1))?)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // positions are less than 16, so the column is always a letter
+        #[allow(clippy::cast_possible_truncation)]
+        let column = char::from(b'a' + self.x() as u8);
+        write!(f, "{column}{}", self.y() + 1)
+    }
+}
+
+/// writes the moves separated by spaces, such as `b2 a1 c3`
+pub fn write_moves(moves: &[Position]) -> String {
+    moves
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// reads moves separated by whitespace, such as `b2 a1 c3`
+///
+/// # Errors
+///
+/// Returns an error if any of the moves isn't a valid position.
+pub fn parse_moves(s: &str) -> Result<Vec<Position>, MoveParseError> {
+    s.split_whitespace().map(str::parse).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::testing::reachable;
+
+    #[test]
+    fn test_board_notation() {
+        let board: Board = "X.O/.X./..O".parse().unwrap();
+        assert_eq!(
+            board,
+            Board::from([
+                [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
+                [Tile::Unplayed, Tile::Cross, Tile::Unplayed],
+                [Tile::Nought, Tile::Unplayed, Tile::Nought],
+            ])
+        );
+        assert_eq!(board.to_string(), "X.O/.X./..O");
+    }
+
+    #[test]
+    fn test_win_length_notation() {
+        let board = Board::with_size(BoardSize::new(4, 2, 4).unwrap());
+        assert_eq!(board.to_string(), "..../....:4");
+        assert_eq!("..../....:4".parse::<Board>().unwrap(), board);
+        assert_eq!("..../....".parse::<Board>().unwrap().win_length, 3);
+    }
+
+    #[test]
+    fn test_invalid_board_notation() {
+        assert!(matches!(
+            "X.O/.X/..O".parse::<Board>(),
+            Err(BoardParseError::UnevenRows)
+        ));
+        assert!(matches!(
+            "X.O/.Y./..O".parse::<Board>(),
+            Err(BoardParseError::InvalidTile('Y'))
+        ));
+        assert!(matches!(
+            ".../.../...:4".parse::<Board>(),
+            Err(BoardParseError::Size(_))
+        ));
+    }
+
+    #[test]
+    fn test_every_reachable_board_round_trips() {
+        let mut seen = HashSet::new();
+        reachable(&Board::new(), Tile::Cross, &mut seen);
+        reachable(&Board::new(), Tile::Nought, &mut seen);
+        for board in seen {
+            assert_eq!(board.to_string().parse::<Board>().unwrap(), board);
+        }
+    }
+
+    #[test]
+    fn test_move_notation() {
+        let moves = parse_moves("b2 a1 c3 p16").unwrap();
+        assert_eq!(
+            moves,
+            [(1, 1), (0, 0), (2, 2), (15, 15)]
+                .map(|xy| Position::try_from(xy).unwrap())
+                .to_vec()
+        );
+        assert_eq!(write_moves(&moves), "b2 a1 c3 p16");
+    }
+
+    #[test]
+    fn test_invalid_move_notation() {
+        assert!("a0".parse::<Position>().is_err());
+        assert!("2b".parse::<Position>().is_err());
+        assert!("q1".parse::<Position>().is_err());
+        assert!("a17".parse::<Position>().is_err());
+    }
+}
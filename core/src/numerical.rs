@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::Position, Board, GameSummary, MoveError, Tile};
+
+/// a 3x3 board where the players place the numbers 1 to 9, one playing the odd numbers and the
+/// other the even ones
+///
+/// Each number can be played once, and a full line adding up to 15 wins for whoever completes it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct NumericalBoard {
+    /// the number in each cell, indexed by x then y
+    pub cells: Vec<Vec<Option<u8>>>,
+    /// the seat playing the odd numbers, who moves first
+    pub odd: Tile,
+}
+
+impl Default for NumericalBoard {
+    fn default() -> Self {
+        Self {
+            cells: vec![vec![None; 3]; 3],
+            odd: Tile::Cross,
+        }
+    }
+}
+
+impl NumericalBoard {
+    /// the numbers that can be played
+    pub const NUMBERS: std::ops::RangeInclusive<u8> = 1..=9;
+    /// what a winning line adds up to
+    pub const TARGET: u8 = 15;
+
+    pub fn clear(&mut self) {
+        self.cells = vec![vec![None; 3]; 3];
+    }
+
+    /// the number in the cell, if one has been played there
+    pub fn number(&self, cell: Position) -> Option<u8> {
+        self.cells
+            .get(cell.x())
+            .and_then(|column| column.get(cell.y()))
+            .copied()
+            .flatten()
+    }
+
+    /// every number on the board
+    fn played(&self) -> impl Iterator<Item = u8> + '_ {
+        self.cells.iter().flatten().copied().flatten()
+    }
+
+    /// the seat that plays the number
+    pub const fn owner(&self, number: u8) -> Tile {
+        if number % 2 == 1 {
+            self.odd
+        } else {
+            self.odd.opponent()
+        }
+    }
+
+    /// the numbers `player` has yet to play, smallest first
+    pub fn remaining(&self, player: Tile) -> Vec<u8> {
+        Self::NUMBERS
+            .filter(|&number| self.owner(number) == player)
+            .filter(|&number| !self.played().any(|n| n == number))
+            .collect()
+    }
+
+    /// the seat that played each number
+    pub fn tiles(&self) -> Board {
+        let mut tiles = Board::new();
+        for cell in Board::new().positions() {
+            if let Some(number) = self.number(cell) {
+                tiles.tiles[cell.x()][cell.y()] = self.owner(number);
+            }
+        }
+        tiles
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary, and the line adding up to 15 if there is one. Since the odd numbers are played
+    /// first, whoever played last is known from how many numbers are on the board, and they
+    /// completed the line.
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        let line = Board::LINES.iter().find(|line| {
+            line.iter()
+                .map(|&cell| self.number(cell))
+                .sum::<Option<u8>>()
+                == Some(Self::TARGET)
+        });
+        let count = self.played().count();
+        match line {
+            Some(line) => {
+                let last = if count % 2 == 1 {
+                    self.odd
+                } else {
+                    self.odd.opponent()
+                };
+                let summary = if last.is_cross() {
+                    GameSummary::CrossWin
+                } else {
+                    GameSummary::NoughtWin
+                };
+                (summary, Some(line.to_vec()))
+            }
+            None if count == Self::NUMBERS.count() => (GameSummary::Tie, None),
+            None => (GameSummary::InProgress, None),
+        }
+    }
+
+    /// checks the cell can be played
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_move(&self, cell: Position) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
+        }
+        if cell.x() >= 3 || cell.y() >= 3 {
+            return Err(MoveError::OutOfBounds);
+        }
+        if self.number(cell).is_some() {
+            return Err(MoveError::Occupied);
+        }
+        Ok(())
+    }
+
+    /// every cell that can be played
+    pub fn legal_moves(&self) -> Vec<Position> {
+        self.tiles()
+            .positions()
+            .filter(|&cell| self.check_move(cell).is_ok())
+            .collect()
+    }
+
+    /// checks `player` can play the number in the cell
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_number(&self, cell: Position, number: u8, player: Tile) -> Result<(), MoveError> {
+        self.check_move(cell)?;
+        if !Self::NUMBERS.contains(&number) {
+            return Err(MoveError::NotANumber);
+        }
+        if self.owner(number) != player {
+            return Err(MoveError::WrongParity);
+        }
+        if self.played().any(|n| n == number) {
+            return Err(MoveError::NumberUsed);
+        }
+        Ok(())
+    }
+
+    /// every cell and number `player` can play
+    pub fn moves(&self, player: Tile) -> Vec<(Position, u8)> {
+        let numbers = self.remaining(player);
+        self.legal_moves()
+            .into_iter()
+            .flat_map(|cell| numbers.iter().map(move |&number| (cell, number)))
+            .collect()
+    }
+
+    /// plays the number in the cell on behalf of `player`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn play(&mut self, cell: Position, number: u8, player: Tile) -> Result<(), MoveError> {
+        self.check_number(cell, number, player)?;
+        self.cells[cell.x()][cell.y()] = Some(number);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pos;
+
+    #[test]
+    fn test_line_adding_up_to_fifteen_wins() {
+        let mut board = NumericalBoard::default();
+        for (xy, number, player) in [
+            ((0, 0), 9, Tile::Cross),
+            ((2, 2), 2, Tile::Nought),
+            ((1, 0), 1, Tile::Cross),
+            ((1, 1), 8, Tile::Nought),
+        ] {
+            board.play(pos(xy.0, xy.1), number, player).unwrap();
+        }
+        assert_eq!(board.summary(), GameSummary::InProgress);
+        assert_eq!(board.remaining(Tile::Cross), [3, 5, 7]);
+
+        board.play(pos(2, 0), 5, Tile::Cross).unwrap();
+        let (summary, line) = board.summary_with_line();
+        assert_eq!(summary, GameSummary::CrossWin);
+        assert_eq!(line, Some(vec![pos(0, 0), pos(1, 0), pos(2, 0)]));
+    }
+
+    #[test]
+    fn test_numbers() {
+        let mut board = NumericalBoard {
+            odd: Tile::Nought,
+            ..Default::default()
+        };
+        assert_eq!(
+            board.play(pos(0, 0), 3, Tile::Cross),
+            Err(MoveError::WrongParity)
+        );
+        assert_eq!(
+            board.play(pos(0, 0), 10, Tile::Cross),
+            Err(MoveError::NotANumber)
+        );
+        board.play(pos(0, 0), 3, Tile::Nought).unwrap();
+        assert_eq!(
+            board.play(pos(1, 0), 3, Tile::Nought),
+            Err(MoveError::NumberUsed)
+        );
+        assert_eq!(
+            board.play(pos(0, 0), 4, Tile::Cross),
+            Err(MoveError::Occupied)
+        );
+        assert_eq!(board.tiles().get(pos(0, 0)), Some(Tile::Nought));
+        assert_eq!(board.moves(Tile::Cross).len(), 8 * 4);
+    }
+
+    #[test]
+    fn test_full_board_ties() {
+        let mut board = NumericalBoard::default();
+        // the odd numbers are spread so no line adds up to 15
+        for (xy, number) in [
+            ((0, 0), 1),
+            ((1, 0), 2),
+            ((2, 0), 3),
+            ((0, 1), 4),
+            ((1, 1), 5),
+            ((2, 1), 7),
+            ((0, 2), 9),
+            ((1, 2), 6),
+            ((2, 2), 8),
+        ] {
+            let player = board.owner(number);
+            board.play(pos(xy.0, xy.1), number, player).unwrap();
+        }
+        assert_eq!(board.summary(), GameSummary::Tie);
+    }
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    gomoku::{Restriction, RestrictionParseError},
+    BoardSize, BoardSizeError, Rules, RulesParseError,
+};
+
+#[derive(Error, Debug)]
+pub enum ModeParseError {
+    #[error(
+        "must be one of standard, ultimate, notakto, gomoku, gravity, qubic, quantum, infinite, \
+         gobblet, orderchaos or numerical"
+    )]
+    Unknown,
+}
+
+/// the kind of game being played
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Mode {
+    /// get enough in a row on a single board
+    #[default]
+    Standard,
+    /// win three small boards in a row, where each move decides the small board the opponent plays in next
+    Ultimate,
+    /// both players play crosses on several boards, and whoever completes a line on the last board loses
+    Notakto,
+    /// five in a row on a 15x15 board
+    Gomoku,
+    /// pieces fall to the bottom of the column they are dropped in, as in connect four
+    Gravity,
+    /// four in a row through a 4x4x4 cube
+    Qubic,
+    /// each move places spooky marks in two cells, which collapse when they make a cycle
+    Quantum,
+    /// each player can only have three pieces on the board, and placing a fourth removes the
+    /// oldest
+    Infinite,
+    /// each player has small, medium and large pieces, and a larger piece can cover a smaller one
+    Gobblet,
+    /// on a 6x6 board, order tries to make five in a row of either symbol and chaos tries to stop
+    /// them
+    OrderChaos,
+    /// one player places the odd numbers and the other the even ones, and a line adding up to 15
+    /// wins
+    Numerical,
+}
+
+impl TryFrom<&str> for Mode {
+    type Error = ModeParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "standard" => Ok(Self::Standard),
+            "ultimate" => Ok(Self::Ultimate),
+            "notakto" => Ok(Self::Notakto),
+            "gomoku" => Ok(Self::Gomoku),
+            "gravity" => Ok(Self::Gravity),
+            "qubic" => Ok(Self::Qubic),
+            "quantum" => Ok(Self::Quantum),
+            "infinite" => Ok(Self::Infinite),
+            "gobblet" => Ok(Self::Gobblet),
+            "orderchaos" => Ok(Self::OrderChaos),
+            "numerical" => Ok(Self::Numerical),
+            _ => Err(Self::Error::Unknown),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GameOptionsParseError {
+    #[error("invalid board size: {0}")]
+    Size(#[from] BoardSizeError),
+    #[error("invalid mode: {0}")]
+    Mode(#[from] ModeParseError),
+    #[error("invalid rules: {0}")]
+    Rules(#[from] RulesParseError),
+    #[error("invalid restriction: {0}")]
+    Restriction(#[from] RestrictionParseError),
+}
+
+/// how a new game is set up, chosen by the player who creates it
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameOptions {
+    pub mode: Mode,
+    #[serde(default)]
+    pub rules: Rules,
+    /// the size of the board in standard and gravity modes
+    pub size: BoardSize,
+    /// the limits on the first player in gomoku mode
+    #[serde(default)]
+    pub restriction: Restriction,
+}
+
+impl TryFrom<&str> for GameOptions {
+    type Error = GameOptionsParseError;
+
+    /// parses the options from a URL query string such as `?mode=standard&size=4,4,4`. Unknown
+    /// parameters are ignored.
+    fn try_from(query: &str) -> Result<Self, Self::Error> {
+        let mut options = Self::default();
+
+        for param in query.trim_start_matches('?').split('&') {
+            let Some((key, value)) = param.split_once('=') else {
+                continue;
+            };
+            // browsers may escape the separators
+            let value = value.replace("%2C", ",").replace("%2c", ",");
+
+            match key {
+                "mode" => options.mode = Mode::try_from(value.as_str())?,
+                "size" => options.size = BoardSize::try_from(value.as_str())?,
+                "rules" => options.rules = Rules::try_from(value.as_str())?,
+                "restriction" => options.restriction = Restriction::try_from(value.as_str())?,
+                _ => {}
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let options = GameOptions::try_from("?bot=hard&size=15%2C15%2C5").unwrap();
+        assert_eq!(options.size, BoardSize::new(15, 15, 5).unwrap());
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        let options = GameOptions::try_from("mode=ultimate").unwrap();
+        assert_eq!(options.mode, Mode::Ultimate);
+        assert_eq!(
+            GameOptions::try_from("mode=notakto").unwrap().mode,
+            Mode::Notakto
+        );
+        assert_eq!(
+            GameOptions::try_from("mode=qubic").unwrap().mode,
+            Mode::Qubic
+        );
+        assert_eq!(
+            GameOptions::try_from("mode=quantum").unwrap().mode,
+            Mode::Quantum
+        );
+        assert_eq!(
+            GameOptions::try_from("mode=infinite").unwrap().mode,
+            Mode::Infinite
+        );
+        assert_eq!(
+            GameOptions::try_from("mode=gobblet").unwrap().mode,
+            Mode::Gobblet
+        );
+        assert_eq!(
+            GameOptions::try_from("mode=orderchaos").unwrap().mode,
+            Mode::OrderChaos
+        );
+        assert_eq!(
+            GameOptions::try_from("mode=numerical").unwrap().mode,
+            Mode::Numerical
+        );
+        assert!(GameOptions::try_from("mode=mega").is_err());
+    }
+
+    #[test]
+    fn test_parse_gomoku() {
+        let options = GameOptions::try_from("mode=gomoku&restriction=renju").unwrap();
+        assert_eq!(options.mode, Mode::Gomoku);
+        assert_eq!(options.restriction, Restriction::Renju);
+        assert!(GameOptions::try_from("restriction=pro").is_err());
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let options = GameOptions::try_from("?rules=misere").unwrap();
+        assert_eq!(options.rules, Rules::Misere);
+        assert_eq!(
+            GameOptions::try_from("rules=wild").unwrap().rules,
+            Rules::Wild
+        );
+        assert!(GameOptions::try_from("rules=reverse").is_err());
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(GameOptions::try_from("").unwrap(), GameOptions::default());
+    }
+
+    #[test]
+    fn test_invalid_size() {
+        assert!(GameOptions::try_from("size=0,0,0").is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::Position, Board, BoardSize, GameSummary, MoveError, Tile};
+
+/// the side a player takes in order and chaos
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// wins with five in a row of either symbol, and moves first
+    Order,
+    /// wins by filling the board without five in a row
+    Chaos,
+}
+
+/// a 6x6 board where both players place either symbol. The seats take the roles of order and
+/// chaos rather than playing crosses and noughts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct OrderChaosBoard {
+    pub board: Board,
+    /// the seat playing order
+    pub order: Tile,
+}
+
+impl Default for OrderChaosBoard {
+    fn default() -> Self {
+        Self {
+            board: Board::with_size(Self::SIZE),
+            order: Tile::Cross,
+        }
+    }
+}
+
+impl OrderChaosBoard {
+    pub const SIZE: BoardSize = BoardSize {
+        width: 6,
+        height: 6,
+        win_length: 5,
+    };
+
+    pub fn clear(&mut self) {
+        self.board.clear();
+    }
+
+    /// the role of the seat
+    pub fn role(&self, player: Tile) -> Option<Role> {
+        if player.is_unplayed() {
+            None
+        } else if player == self.order {
+            Some(Role::Order)
+        } else {
+            Some(Role::Chaos)
+        }
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary for the seats, and the line that won the game for order if there is one. A full
+    /// board without a line is a win for chaos.
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        let (summary, line) = self.board.summary_with_line();
+        let winner = match summary {
+            GameSummary::InProgress => return (summary, None),
+            GameSummary::CrossWin | GameSummary::NoughtWin => self.order,
+            GameSummary::Tie => self.order.opponent(),
+        };
+        let summary = if winner.is_cross() {
+            GameSummary::CrossWin
+        } else {
+            GameSummary::NoughtWin
+        };
+        (summary, line)
+    }
+
+    /// checks the position can be played
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_move(&self, pos: Position) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
+        }
+        self.board.check_move(pos)
+    }
+
+    /// places either symbol at the position
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn play(&mut self, pos: Position, symbol: Tile) -> Result<(), MoveError> {
+        self.check_move(pos)?;
+        self.board.play(pos, symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_of_either_symbol_wins_for_order() {
+        let mut board = OrderChaosBoard {
+            order: Tile::Nought,
+            ..Default::default()
+        };
+        for x in 0..5 {
+            assert_eq!(board.summary(), GameSummary::InProgress);
+            board.play((x, 2).try_into().unwrap(), Tile::Cross).unwrap();
+        }
+        let (summary, line) = board.summary_with_line();
+        assert_eq!(summary, GameSummary::NoughtWin);
+        assert_eq!(line.map(|line| line.len()), Some(5));
+        assert_eq!(board.role(Tile::Cross), Some(Role::Chaos));
+    }
+
+    #[test]
+    fn test_full_board_wins_for_chaos() {
+        let mut board = OrderChaosBoard::default();
+        // pairs of columns alternate, so no five in a row in any direction
+        for x in 0..6 {
+            for y in 0..6 {
+                let symbol = if (x / 2 + y) % 2 == 0 {
+                    Tile::Cross
+                } else {
+                    Tile::Nought
+                };
+                board.play((x, y).try_into().unwrap(), symbol).unwrap();
+            }
+        }
+        assert_eq!(board.summary(), GameSummary::NoughtWin);
+    }
+}
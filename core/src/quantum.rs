@@ -0,0 +1,397 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::Position, Board, GameSummary, MoveError, Tile};
+
+/// a move in a quantum game
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum QuantumMove {
+    /// a spooky mark in both cells. When only one cell is left the cells are the same, and the
+    /// mark is classical straight away.
+    Entangle(Position, Position),
+    /// chooses which of its cells the move that closed a cycle collapses into
+    Collapse(Position),
+}
+
+impl QuantumMove {
+    /// the cell the move is recorded at: the first cell of an entanglement
+    pub const fn position(self) -> Position {
+        match self {
+            Self::Entangle(pos, _) | Self::Collapse(pos) => pos,
+        }
+    }
+}
+
+/// a mark played in two cells at once, until a measurement collapses it into one of them
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct QuantumMark {
+    pub tile: Tile,
+    pub cells: [Position; 2],
+    /// the cell the mark collapsed into, once it is classical
+    pub collapsed: Option<Position>,
+}
+
+impl QuantumMark {
+    /// the cell the mark is entangled with, if it is in `cell`
+    pub fn other(&self, cell: Position) -> Option<Position> {
+        match self.cells {
+            [a, b] if a == cell => Some(b),
+            [a, b] if b == cell => Some(a),
+            _ => None,
+        }
+    }
+}
+
+/// quantum noughts and crosses, where each move places spooky marks in two cells
+///
+/// When the entanglements make a cycle, the opponent of whoever closed it chooses where the last move
+/// collapses, which decides every mark in the cycle and anything entangled with it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct QuantumBoard {
+    /// every mark in the order played. A mark's subscript is its place in the order, from 1.
+    pub marks: Vec<QuantumMark>,
+}
+
+impl QuantumBoard {
+    pub fn clear(&mut self) {
+        self.marks.clear();
+    }
+
+    /// the classical mark in the cell and its subscript, once a mark has collapsed into it
+    pub fn classical(&self, cell: Position) -> Option<(Tile, usize)> {
+        self.marks
+            .iter()
+            .position(|mark| mark.collapsed == Some(cell))
+            .map(|i| (self.marks[i].tile, i + 1))
+    }
+
+    /// the spooky marks still in the cell and their subscripts
+    pub fn spooky(&self, cell: Position) -> impl Iterator<Item = (Tile, usize)> + '_ {
+        self.marks
+            .iter()
+            .enumerate()
+            .filter(move |(_, mark)| mark.collapsed.is_none() && mark.cells.contains(&cell))
+            .map(|(i, mark)| (mark.tile, i + 1))
+    }
+
+    /// whether the spooky marks entangle the cells, directly or through other cells
+    pub(crate) fn connected(marks: &[QuantumMark], from: Position, to: Position) -> bool {
+        let mut seen = vec![from];
+        let mut unvisited = vec![from];
+        while let Some(cell) = unvisited.pop() {
+            if cell == to {
+                return true;
+            }
+            for next in marks
+                .iter()
+                .filter(|mark| mark.collapsed.is_none())
+                .filter_map(|mark| mark.other(cell))
+            {
+                if !seen.contains(&next) {
+                    seen.push(next);
+                    unvisited.push(next);
+                }
+            }
+        }
+        false
+    }
+
+    /// the cells the last move can collapse into, if it closed a cycle that hasn't been collapsed
+    pub fn pending_collapse(&self) -> Option<[Position; 2]> {
+        let (last, before) = self.marks.split_last()?;
+        let [a, b] = last.cells;
+        (last.collapsed.is_none() && Self::connected(before, a, b)).then_some(last.cells)
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary, and the line that won the game if there is one. A collapse can complete lines
+    /// for both players at once, in which case the line finished with the earlier marks wins.
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        let winner = Board::LINES
+            .iter()
+            .filter_map(|line| {
+                let marks = line
+                    .iter()
+                    .map(|&cell| self.classical(cell))
+                    .collect::<Option<Vec<_>>>()?;
+                let tile = marks[0].0;
+                let latest = marks.iter().map(|&(_, subscript)| subscript).max()?;
+                marks
+                    .iter()
+                    .all(|&(t, _)| t == tile)
+                    .then_some((latest, tile, line))
+            })
+            .min_by_key(|&(latest, ..)| latest);
+
+        match winner {
+            Some((_, Tile::Cross, line)) => (GameSummary::CrossWin, Some(line.to_vec())),
+            Some((_, Tile::Nought, line)) => (GameSummary::NoughtWin, Some(line.to_vec())),
+            _ if self
+                .tiles()
+                .positions()
+                .all(|cell| self.classical(cell).is_some()) =>
+            {
+                (GameSummary::Tie, None)
+            }
+            _ => (GameSummary::InProgress, None),
+        }
+    }
+
+    /// the classical marks on a 3x3 board
+    pub fn tiles(&self) -> Board {
+        let mut tiles = Board::new();
+        for mark in &self.marks {
+            if let Some(cell) = mark.collapsed {
+                tiles.tiles[cell.x()][cell.y()] = mark.tile;
+            }
+        }
+        tiles
+    }
+
+    /// checks the cell can be played: half of a move, or where the last move collapses when it
+    /// closed a cycle
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_move(&self, cell: Position) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
+        }
+        if cell.x() >= 3 || cell.y() >= 3 {
+            return Err(MoveError::OutOfBounds);
+        }
+        if let Some(choices) = self.pending_collapse() {
+            return if choices.contains(&cell) {
+                Ok(())
+            } else {
+                Err(MoveError::Uncollapsed)
+            };
+        }
+        if self.classical(cell).is_some() {
+            return Err(MoveError::Occupied);
+        }
+        Ok(())
+    }
+
+    /// every cell that can be played
+    pub fn legal_moves(&self) -> Vec<Position> {
+        self.tiles()
+            .positions()
+            .filter(|&cell| self.check_move(cell).is_ok())
+            .collect()
+    }
+
+    /// checks spooky marks can be placed in both cells
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_entangle(&self, a: Position, b: Position) -> Result<(), MoveError> {
+        if self.pending_collapse().is_some() {
+            return Err(MoveError::Uncollapsed);
+        }
+        self.check_move(a)?;
+        self.check_move(b)?;
+        // the last cell can only be played classically
+        if a == b && self.legal_moves().len() > 1 {
+            return Err(MoveError::SameCell);
+        }
+        Ok(())
+    }
+
+    /// places spooky marks of the tile in both cells
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn entangle(&mut self, a: Position, b: Position, tile: Tile) -> Result<(), MoveError> {
+        if tile.is_unplayed() {
+            return Err(MoveError::Unplayed);
+        }
+        self.check_entangle(a, b)?;
+        self.marks.push(QuantumMark {
+            tile,
+            cells: [a, b],
+            collapsed: (a == b).then_some(a),
+        });
+        Ok(())
+    }
+
+    /// collapses the move that closed a cycle into the cell. Every other spooky mark in a cell
+    /// that becomes classical collapses into its other cell, and so on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no cycle, or the last move wasn't played in the cell. The board
+    /// is left unchanged.
+    pub fn collapse(&mut self, cell: Position) -> Result<(), MoveError> {
+        let choices = self
+            .pending_collapse()
+            .ok_or(MoveError::NothingToCollapse)?;
+        if !choices.contains(&cell) {
+            return Err(MoveError::NotEntangled);
+        }
+
+        let mut measured = vec![(self.marks.len() - 1, cell)];
+        while let Some((i, cell)) = measured.pop() {
+            if self.marks[i].collapsed.is_some() {
+                continue;
+            }
+            self.marks[i].collapsed = Some(cell);
+            for (j, mark) in self.marks.iter().enumerate() {
+                if mark.collapsed.is_none() {
+                    if let Some(other) = mark.other(cell) {
+                        measured.push((j, other));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// every entanglement or collapse that can be played
+    pub fn moves(&self) -> Vec<QuantumMove> {
+        if self.summary().is_finished() {
+            return Vec::new();
+        }
+        if let Some(choices) = self.pending_collapse() {
+            return choices.map(QuantumMove::Collapse).to_vec();
+        }
+        let cells = self.legal_moves();
+        if let [cell] = cells[..] {
+            return vec![QuantumMove::Entangle(cell, cell)];
+        }
+        cells
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &a)| {
+                cells[i + 1..]
+                    .iter()
+                    .map(move |&b| QuantumMove::Entangle(a, b))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pos;
+
+    // the two cells a quantum move is played in
+    type Pair = ((usize, usize), (usize, usize));
+
+    /// plays the cell pairs, taking turns from crosses
+    fn board(pairs: &[Pair]) -> QuantumBoard {
+        let mut board = QuantumBoard::default();
+        let mut tile = Tile::Cross;
+        for &(a, b) in pairs {
+            board.entangle(pos(a.0, a.1), pos(b.0, b.1), tile).unwrap();
+            tile = tile.opponent();
+        }
+        board
+    }
+
+    #[test]
+    fn test_cycle_collapses() {
+        let mut board = board(&[((0, 0), (1, 1)), ((1, 1), (2, 2))]);
+        assert_eq!(board.pending_collapse(), None);
+        assert_eq!(board.spooky(pos(1, 1)).count(), 2);
+        assert_eq!(board.collapse(pos(0, 0)), Err(MoveError::NothingToCollapse));
+
+        // closes the cycle (0, 0) - (1, 1) - (2, 2)
+        board.entangle(pos(2, 2), pos(0, 0), Tile::Cross).unwrap();
+        assert_eq!(board.pending_collapse(), Some([pos(2, 2), pos(0, 0)]));
+        assert_eq!(
+            board.entangle(pos(0, 1), pos(0, 2), Tile::Nought),
+            Err(MoveError::Uncollapsed)
+        );
+        assert_eq!(board.collapse(pos(1, 1)), Err(MoveError::NotEntangled));
+
+        board.collapse(pos(2, 2)).unwrap();
+        assert_eq!(board.classical(pos(2, 2)), Some((Tile::Cross, 3)));
+        assert_eq!(board.classical(pos(1, 1)), Some((Tile::Nought, 2)));
+        assert_eq!(board.classical(pos(0, 0)), Some((Tile::Cross, 1)));
+        assert_eq!(board.spooky(pos(1, 1)).count(), 0);
+        assert_eq!(board.pending_collapse(), None);
+        assert_eq!(board.check_move(pos(1, 1)), Err(MoveError::Occupied));
+    }
+
+    #[test]
+    fn test_collapse_follows_entanglements() {
+        // (0, 2) hangs off the cycle, so it is decided too
+        let mut board = board(&[
+            ((0, 0), (1, 0)),
+            ((1, 0), (0, 2)),
+            ((1, 0), (2, 0)),
+            ((2, 0), (0, 0)),
+        ]);
+        board.collapse(pos(0, 0)).unwrap();
+        assert_eq!(board.classical(pos(0, 0)), Some((Tile::Nought, 4)));
+        assert_eq!(board.classical(pos(1, 0)), Some((Tile::Cross, 1)));
+        assert_eq!(board.classical(pos(0, 2)), Some((Tile::Nought, 2)));
+        assert_eq!(board.classical(pos(2, 0)), Some((Tile::Cross, 3)));
+    }
+
+    /// classical marks in the cells, in order
+    fn classical(cells: &[((usize, usize), Tile)]) -> QuantumBoard {
+        QuantumBoard {
+            marks: cells
+                .iter()
+                .map(|&((x, y), tile)| QuantumMark {
+                    tile,
+                    cells: [pos(x, y), pos(x, y)],
+                    collapsed: Some(pos(x, y)),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_earlier_line_wins() {
+        // noughts finished their line with mark 5, before crosses with mark 6
+        let board = classical(&[
+            ((0, 0), Tile::Cross),
+            ((0, 1), Tile::Nought),
+            ((1, 0), Tile::Cross),
+            ((1, 1), Tile::Nought),
+            ((2, 1), Tile::Nought),
+            ((2, 0), Tile::Cross),
+        ]);
+        let (summary, line) = board.summary_with_line();
+        assert_eq!(summary, GameSummary::NoughtWin);
+        assert_eq!(line, Some(vec![pos(0, 1), pos(1, 1), pos(2, 1)]));
+    }
+
+    #[test]
+    fn test_last_cell_is_classical() {
+        let mut board = classical(&[
+            ((0, 0), Tile::Cross),
+            ((1, 0), Tile::Nought),
+            ((2, 0), Tile::Cross),
+            ((0, 1), Tile::Cross),
+            ((1, 1), Tile::Nought),
+            ((2, 1), Tile::Nought),
+            ((0, 2), Tile::Nought),
+            ((1, 2), Tile::Cross),
+        ]);
+        let last = pos(2, 2);
+        assert_eq!(board.moves(), [QuantumMove::Entangle(last, last)]);
+        board.entangle(last, last, Tile::Cross).unwrap();
+        assert_eq!(board.classical(last), Some((Tile::Cross, 9)));
+        assert_eq!(board.summary(), GameSummary::Tie);
+    }
+
+    #[test]
+    fn test_same_cell() {
+        let board = QuantumBoard::default();
+        assert_eq!(
+            board.check_entangle(pos(0, 0), pos(0, 0)),
+            Err(MoveError::SameCell)
+        );
+        assert_eq!(board.moves().len(), 36);
+    }
+}
@@ -0,0 +1,244 @@
+use std::{ops::Range, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::Position, Board, BoardSize, GameSummary, MoveError, Tile};
+
+/// a 4x4x4 cube played as four stacked 4x4 layers. Four in a row along any of the 76 lines
+/// through the cube wins, including the lines that pass through every layer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct QubicBoard {
+    /// from the bottom layer up
+    pub layers: Vec<Board>,
+}
+
+impl Default for QubicBoard {
+    fn default() -> Self {
+        Self {
+            layers: vec![Board::with_size(Self::LAYER); Self::SIZE],
+        }
+    }
+}
+
+/// every combination of three values from the range
+fn triples<T>(range: Range<T>) -> impl Iterator<Item = (T, T, T)>
+where
+    T: Copy + 'static,
+    Range<T>: Iterator<Item = T> + Clone,
+{
+    let (middle, inner) = (range.clone(), range.clone());
+    range.flat_map(move |a| {
+        let inner = inner.clone();
+        middle
+            .clone()
+            .flat_map(move |b| inner.clone().map(move |c| (a, b, c)))
+    })
+}
+
+impl QubicBoard {
+    /// the width, height and depth of the cube, and the length of a line
+    pub const SIZE: usize = 4;
+    pub const LAYER: BoardSize = BoardSize {
+        width: Self::SIZE,
+        height: Self::SIZE,
+        win_length: Self::SIZE,
+    };
+
+    pub fn clear(&mut self) {
+        for layer in &mut self.layers {
+            layer.clear();
+        }
+    }
+
+    /// the index of the layer and the position within it. Layers are laid out side by side from
+    /// left to right, bottom first.
+    pub const fn split(pos: Position) -> (usize, Position) {
+        // positions within a layer are less than 4, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        (
+            pos.x() / Self::SIZE,
+            Position::new((pos.x() % Self::SIZE) as u8, pos.y() as u8),
+        )
+    }
+
+    /// the cell `i` steps along the direction from the start, where cells are `(x, y, layer)`
+    fn step(
+        (x, y, z): (usize, usize, usize),
+        (dx, dy, dz): (isize, isize, isize),
+        i: isize,
+    ) -> Option<Position> {
+        let x = x.checked_add_signed(dx * i)?;
+        let y = y.checked_add_signed(dy * i)?;
+        let z = z.checked_add_signed(dz * i)?;
+        if x.max(y).max(z) >= Self::SIZE {
+            return None;
+        }
+        Position::try_from((z * Self::SIZE + x, y)).ok()
+    }
+
+    /// every line through the cube: along rows, columns and layers, the diagonals of each plane,
+    /// and the four diagonals through the middle of the cube. They are found the first time
+    /// they're needed.
+    pub fn lines() -> &'static [[Position; Self::SIZE]] {
+        static LINES: OnceLock<Vec<[Position; QubicBoard::SIZE]>> = OnceLock::new();
+        LINES.get_or_init(|| {
+            // each line is found once, from the end where the first step that isn't zero is
+            // positive
+            let directions = triples(-1..2).filter(|&direction| direction > (0, 0, 0));
+            directions
+                .flat_map(|direction| {
+                    triples(0..Self::SIZE).filter_map(move |start| {
+                        let line: Vec<Position> = (0..)
+                            .map_while(|i| Self::step(start, direction, i))
+                            .take(Self::SIZE)
+                            .collect();
+                        line.try_into().ok()
+                    })
+                })
+                .collect()
+        })
+    }
+
+    pub fn get(&self, pos: Position) -> Option<Tile> {
+        let (i, inner) = Self::split(pos);
+        self.layers.get(i)?.get(inner)
+    }
+
+    /// the tile that fills the line, if one does
+    pub fn winner(&self, line: &[Position; Self::SIZE]) -> Option<Tile> {
+        let tile = self.get(line[0]).filter(|tile| !tile.is_unplayed())?;
+        line.iter()
+            .all(|&pos| self.get(pos) == Some(tile))
+            .then_some(tile)
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary, and the line that won the game if there is one
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        for line in Self::lines() {
+            match self.winner(line) {
+                Some(Tile::Cross) => return (GameSummary::CrossWin, Some(line.to_vec())),
+                Some(Tile::Nought) => return (GameSummary::NoughtWin, Some(line.to_vec())),
+                _ => {}
+            }
+        }
+
+        if self
+            .layers
+            .iter()
+            .flat_map(|layer| layer.tiles.iter().flatten())
+            .all(|tile| !tile.is_unplayed())
+        {
+            (GameSummary::Tie, None)
+        } else {
+            (GameSummary::InProgress, None)
+        }
+    }
+
+    /// every layer laid out side by side on a single board
+    pub fn tiles(&self) -> Board {
+        let mut tiles = Board {
+            tiles: vec![vec![Tile::Unplayed; Self::SIZE]; Self::SIZE * self.layers.len()],
+            win_length: Self::SIZE,
+        };
+        for pos in tiles.clone().positions() {
+            tiles.tiles[pos.x()][pos.y()] = self.get(pos).unwrap_or_default();
+        }
+        tiles
+    }
+
+    /// checks the position can be played
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the game is over, or the position is off the cube or already played.
+    pub fn check_move(&self, pos: Position) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
+        }
+        match self.get(pos) {
+            None => Err(MoveError::OutOfBounds),
+            Some(Tile::Unplayed) => Ok(()),
+            Some(_) => Err(MoveError::Occupied),
+        }
+    }
+
+    /// every position that can be played
+    pub fn legal_moves(&self) -> Vec<Position> {
+        if self.summary().is_finished() {
+            return Vec::new();
+        }
+        self.tiles()
+            .positions()
+            .filter(|&pos| self.get(pos) == Some(Tile::Unplayed))
+            .collect()
+    }
+
+    /// plays the tile at the position
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn play(&mut self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        if tile.is_unplayed() {
+            return Err(MoveError::Unplayed);
+        }
+        self.check_move(pos)?;
+        let (i, inner) = Self::split(pos);
+        self.layers[i].tiles[inner.x()][inner.y()] = tile;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the position of the cell `(x, y, layer)`
+    fn pos(x: usize, y: usize, layer: usize) -> Position {
+        (layer * QubicBoard::SIZE + x, y).try_into().unwrap()
+    }
+
+    #[test]
+    fn test_lines() {
+        let lines = QubicBoard::lines();
+        assert_eq!(lines.len(), 76);
+        // the corners and middle cells are on seven lines, the rest on four
+        let count = |cell| lines.iter().filter(|line| line.contains(&cell)).count();
+        assert_eq!(count(pos(0, 0, 0)), 7);
+        assert_eq!(count(pos(1, 1, 1)), 7);
+        assert_eq!(count(pos(1, 0, 0)), 4);
+    }
+
+    #[test]
+    fn test_line_through_layers() {
+        let mut board = QubicBoard::default();
+        for layer in 0..3 {
+            board.play(pos(layer, 3, layer), Tile::Cross).unwrap();
+            assert_eq!(board.summary(), GameSummary::InProgress);
+        }
+        assert_eq!(
+            board.play(pos(0, 3, 0), Tile::Nought),
+            Err(MoveError::Occupied)
+        );
+        board.play(pos(3, 3, 3), Tile::Cross).unwrap();
+        let (summary, line) = board.summary_with_line();
+        assert_eq!(summary, GameSummary::CrossWin);
+        assert_eq!(line, Some((0..4).map(|i| pos(i, 3, i)).collect()));
+        assert_eq!(board.legal_moves(), []);
+    }
+
+    #[test]
+    fn test_split() {
+        let board = QubicBoard::default();
+        assert_eq!(
+            QubicBoard::split(pos(1, 2, 3)),
+            (3, (1, 2).try_into().unwrap())
+        );
+        assert_eq!(board.tiles().width(), 16);
+        assert_eq!(board.legal_moves().len(), 64);
+    }
+}
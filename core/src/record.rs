@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::Game, game_state::Position, gobblet::GobbletMove, options::GameOptions,
+    quantum::QuantumMove, GameSummary, MoveError, Tile,
+};
+
+/// what a player played, in whichever mode the game is
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Play {
+    /// a symbol placed at the position. Only wild games allow it to be the other player's.
+    Place {
+        position: Position,
+        symbol: Tile,
+    },
+    Quantum(QuantumMove),
+    Gobblet(GobbletMove),
+    /// a number played at the position in numerical games
+    Number(Position, u8),
+}
+
+impl Play {
+    /// where the move was played: the first cell of a quantum move, or where a gobblet piece went
+    pub const fn position(self) -> Position {
+        match self {
+            Self::Place { position, .. } | Self::Number(position, _) => position,
+            Self::Quantum(m) => m.position(),
+            Self::Gobblet(m) => m.to,
+        }
+    }
+}
+
+/// a move in a game record
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Move {
+    pub play: Play,
+    /// the player who moved
+    pub tile: Tile,
+    /// milliseconds since the epoch
+    pub played_at: u64,
+}
+
+/// everything needed to replay a game: how it was set up, who started and every move in order
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameRecord {
+    pub options: GameOptions,
+    /// the tile that moved first
+    pub starter: Tile,
+    /// milliseconds since the epoch
+    pub started_at: u64,
+    pub moves: Vec<Move>,
+    pub result: GameSummary,
+    /// milliseconds since the epoch, once the game is finished
+    pub finished_at: Option<u64>,
+}
+
+impl GameRecord {
+    /// a record of a game that has just started. Timestamps are milliseconds since the epoch.
+    pub fn new(options: GameOptions, starter: Tile, started_at: u64) -> Self {
+        Self {
+            options,
+            starter,
+            started_at,
+            ..Default::default()
+        }
+    }
+
+    /// the number of moves played
+    pub const fn plies(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// adds a move, which should already have been played on the game it records. Once the game
+    /// is finished its result is recorded too.
+    pub fn push(&mut self, play: Play, player: Tile, played_at: u64, game: &Game) {
+        self.moves.push(Move {
+            play,
+            tile: player,
+            played_at,
+        });
+        self.record_result(played_at, game);
+    }
+
+    /// records the result once the game is finished
+    fn record_result(&mut self, played_at: u64, game: &Game) {
+        let summary = game.summary();
+        if summary.is_finished() {
+            self.result = summary;
+            self.finished_at = Some(played_at);
+        }
+    }
+
+    /// replays the game up to, but not including, move `ply`. Plies past the end give the latest
+    /// position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record contains an illegal move.
+    pub fn game_at(&self, ply: usize) -> Result<Game, MoveError> {
+        let mut game = Game::new(&self.options);
+        // the roles that move first, such as order in order and chaos, go to the starter
+        game.assign_roles(self.starter);
+        for m in self.moves.iter().take(ply) {
+            game.apply(m.play, m.tile)?;
+        }
+        Ok(game)
+    }
+
+    /// replays every move
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record contains an illegal move.
+    pub fn game(&self) -> Result<Game, MoveError> {
+        self.game_at(self.plies())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gobblet::Size, testing::pos};
+
+    fn play(record: &mut GameRecord, game: &mut Game, moves: &[(usize, usize)]) {
+        let mut tile = record.starter;
+        for (i, &xy) in moves.iter().enumerate() {
+            let pos = xy.try_into().unwrap();
+            game.play(pos, tile).unwrap();
+            record.push(
+                Play::Place {
+                    position: pos,
+                    symbol: tile,
+                },
+                tile,
+                i as u64,
+                game,
+            );
+            tile = tile.opponent();
+        }
+    }
+
+    #[test]
+    fn test_reconstructs_each_ply() {
+        let options = GameOptions::default();
+        let mut record = GameRecord::new(options, Tile::Nought, 0);
+        let mut game = Game::new(&options);
+        play(&mut record, &mut game, &[(1, 1), (0, 0), (2, 2)]);
+
+        assert_eq!(record.game_at(0).unwrap(), Game::new(&options));
+        let after_one = record.game_at(1).unwrap().tiles();
+        assert_eq!(
+            after_one.get((1, 1).try_into().unwrap()),
+            Some(Tile::Nought)
+        );
+        assert_eq!(
+            after_one.get((0, 0).try_into().unwrap()),
+            Some(Tile::Unplayed)
+        );
+        assert_eq!(record.game().unwrap(), game);
+        assert_eq!(record.result, GameSummary::InProgress);
+        assert_eq!(record.finished_at, None);
+    }
+
+    #[test]
+    fn test_records_result() {
+        let options = GameOptions::default();
+        let mut record = GameRecord::new(options, Tile::Cross, 0);
+        let mut game = Game::new(&options);
+        play(
+            &mut record,
+            &mut game,
+            &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)],
+        );
+
+        assert_eq!(record.result, GameSummary::CrossWin);
+        assert_eq!(record.finished_at, Some(4));
+        assert_eq!(record.plies(), 5);
+    }
+
+    #[test]
+    fn test_wild_record() {
+        let options = GameOptions::try_from("rules=wild").unwrap();
+        let mut record = GameRecord::new(options, Tile::Cross, 0);
+        let mut game = Game::new(&options);
+        let pos = (1, 1).try_into().unwrap();
+        game.place(pos, Tile::Nought, Tile::Cross).unwrap();
+        let play = Play::Place {
+            position: pos,
+            symbol: Tile::Nought,
+        };
+        record.push(play, Tile::Cross, 0, &game);
+
+        assert_eq!(record.moves[0].play, play);
+        assert_eq!(record.game().unwrap(), game);
+    }
+
+    #[test]
+    fn test_illegal_record() {
+        let mut record = GameRecord::new(GameOptions::default(), Tile::Cross, 0);
+        let pos = (0, 0).try_into().unwrap();
+        for tile in [Tile::Cross, Tile::Nought] {
+            let play = Play::Place {
+                position: pos,
+                symbol: tile,
+            };
+            record.push(play, tile, 0, &Game::default());
+        }
+        assert_eq!(record.game(), Err(MoveError::Occupied));
+    }
+
+    #[test]
+    fn test_quantum_record() {
+        let options = GameOptions::try_from("mode=quantum").unwrap();
+        let mut record = GameRecord::new(options, Tile::Cross, 0);
+        let mut game = Game::new(&options);
+        for (m, player) in [
+            (QuantumMove::Entangle(pos(0, 0), pos(2, 2)), Tile::Cross),
+            (QuantumMove::Entangle(pos(2, 2), pos(0, 0)), Tile::Nought),
+            (QuantumMove::Collapse(pos(0, 0)), Tile::Cross),
+        ] {
+            game.play_quantum(m, player).unwrap();
+            record.push(Play::Quantum(m), player, 0, &game);
+        }
+        assert_eq!(record.game().unwrap(), game);
+        assert_eq!(record.moves[2].play.position(), pos(0, 0));
+    }
+
+    #[test]
+    fn test_gobblet_record() {
+        let options = GameOptions::try_from("mode=gobblet").unwrap();
+        let mut record = GameRecord::new(options, Tile::Cross, 0);
+        let mut game = Game::new(&options);
+        for (size, from, to, player) in [
+            (Size::Small, None, pos(1, 1), Tile::Cross),
+            (Size::Medium, None, pos(1, 1), Tile::Nought),
+            (Size::Small, None, pos(0, 0), Tile::Cross),
+            (Size::Medium, Some(pos(1, 1)), pos(0, 0), Tile::Nought),
+        ] {
+            let m = GobbletMove { size, from, to };
+            game.play_gobblet(m, player).unwrap();
+            record.push(Play::Gobblet(m), player, 0, &game);
+        }
+        assert_eq!(record.game().unwrap(), game);
+        assert_eq!(record.moves[3].play.position(), pos(0, 0));
+    }
+
+    #[test]
+    fn test_numerical_record() {
+        let options = GameOptions::try_from("mode=numerical").unwrap();
+        let mut record = GameRecord::new(options, Tile::Nought, 0);
+        let mut game = Game::new(&options);
+        game.assign_roles(Tile::Nought);
+        for (xy, number, player) in [
+            ((1, 1), 5, Tile::Nought),
+            ((0, 0), 2, Tile::Cross),
+            ((2, 2), 9, Tile::Nought),
+        ] {
+            let pos = xy.try_into().unwrap();
+            game.play_number(pos, number, player).unwrap();
+            record.push(Play::Number(pos, number), player, 0, &game);
+        }
+        assert_eq!(record.game().unwrap(), game);
+        assert_eq!(
+            record.moves[1].play,
+            Play::Number((0, 0).try_into().unwrap(), 2)
+        );
+    }
+}
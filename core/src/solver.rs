@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bitboard::{BitBoard, BitBoardError, Lines},
+    game_state::Position,
+    Board, GameSummary, Rules, Tile,
+};
+
+/// the outcome of a position for the side to move, assuming both sides play perfectly
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Value {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Value {
+    /// the value of the same position for the opponent
+    #[must_use]
+    pub const fn flip(self) -> Self {
+        match self {
+            Self::Loss => Self::Win,
+            Self::Draw => Self::Draw,
+            Self::Win => Self::Loss,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub value: Value,
+    /// every move that achieves the value, and the symbol to place. Empty if the game is
+    /// finished.
+    pub moves: Vec<(Position, Tile)>,
+}
+
+/// negamax search over the game tree. Solved positions are remembered, so keep the solver around
+/// to make repeated searches cheap.
+#[derive(Debug, Default, Clone)]
+pub struct Solver {
+    rules: Rules,
+    cache: HashMap<(BitBoard, Tile), Value>,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// a solver for games with different rules
+    pub fn with_rules(rules: Rules) -> Self {
+        Self {
+            rules,
+            ..Self::default()
+        }
+    }
+
+    /// the value of the board and the optimal moves for `to_move`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the board is too large to search.
+    pub fn solve(&mut self, board: &Board, to_move: Tile) -> Result<Solution, BitBoardError> {
+        let lines = Lines::new(board.size())?;
+        let bits = BitBoard::try_from(board)?;
+        let value = self.search(&lines, bits, to_move);
+
+        let moves = if bits.summary(&lines).is_finished() {
+            Vec::new()
+        } else {
+            Self::moves(&lines, bits, self.rules.symbols(to_move))
+                .filter(|&(_, next)| self.search(&lines, next, to_move.opponent()).flip() == value)
+                .map(|(placement, _)| placement)
+                .collect()
+        };
+
+        Ok(Solution { value, moves })
+    }
+
+    /// the value of the board for `to_move`. The search is exhaustive, so only small boards or
+    /// nearly finished games can be solved in a reasonable time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the board is too large to search.
+    pub fn value(&mut self, board: &Board, to_move: Tile) -> Result<Value, BitBoardError> {
+        let lines = Lines::new(board.size())?;
+        Ok(self.search(&lines, BitBoard::try_from(board)?, to_move))
+    }
+
+    fn search(&mut self, lines: &Lines, board: BitBoard, to_move: Tile) -> Value {
+        let key = (board, to_move);
+        if let Some(value) = self.cache.get(&key) {
+            return *value;
+        }
+
+        let mover = Some(to_move.opponent());
+        let value = match self.rules.apply(board.summary(lines), mover) {
+            GameSummary::InProgress => {
+                let mut best = Value::Loss;
+                for (_, next) in Self::moves(lines, board, self.rules.symbols(to_move)) {
+                    best = best.max(self.search(lines, next, to_move.opponent()).flip());
+                    if best == Value::Win {
+                        break;
+                    }
+                }
+                best
+            }
+            GameSummary::NoughtWin if to_move.is_nought() => Value::Win,
+            GameSummary::CrossWin if to_move.is_cross() => Value::Win,
+            GameSummary::NoughtWin | GameSummary::CrossWin => Value::Loss,
+            GameSummary::Tie => Value::Draw,
+        };
+
+        self.cache.insert(key, value);
+        value
+    }
+
+    /// every unplayed tile and symbol, and the board after the symbol is placed there
+    fn moves(
+        lines: &Lines,
+        board: BitBoard,
+        symbols: &'static [Tile],
+    ) -> impl Iterator<Item = ((Position, Tile), BitBoard)> {
+        board.empty(lines).flat_map(move |pos| {
+            symbols.iter().filter_map(move |&symbol| {
+                let mut next = board;
+                next.play(pos, symbol).ok()?;
+                Some(((pos, symbol), next))
+            })
+        })
+    }
+}
+
+/// solves a single board. Use a [`Solver`] to reuse work between searches.
+///
+/// # Errors
+///
+/// Returns an error if the board is too large to search.
+pub fn solve(board: &Board, to_move: Tile) -> Result<Solution, BitBoardError> {
+    Solver::new().solve(board, to_move)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placements(moves: &[(usize, usize)], tile: Tile) -> Vec<(Position, Tile)> {
+        moves
+            .iter()
+            .map(|&m| (m.try_into().unwrap(), tile))
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_board_is_a_draw() {
+        let solution = solve(&Board::new(), Tile::Cross).unwrap();
+        assert_eq!(solution.value, Value::Draw);
+        // every opening move holds the draw
+        assert_eq!(solution.moves.len(), 9);
+    }
+
+    #[test]
+    fn test_takes_the_win() {
+        let board = Board::from([
+            [Tile::Cross, Tile::Cross, Tile::Unplayed],
+            [Tile::Nought, Tile::Nought, Tile::Unplayed],
+            [Tile::Unplayed; 3],
+        ]);
+        let solution = solve(&board, Tile::Cross).unwrap();
+        assert_eq!(solution.value, Value::Win);
+        assert_eq!(solution.moves, placements(&[(0, 2)], Tile::Cross));
+    }
+
+    #[test]
+    fn test_blocks_the_fork() {
+        // crosses in opposite corners, noughts must play an edge to hold the draw
+        let board = Board::from([
+            [Tile::Cross, Tile::Unplayed, Tile::Unplayed],
+            [Tile::Unplayed, Tile::Nought, Tile::Unplayed],
+            [Tile::Unplayed, Tile::Unplayed, Tile::Cross],
+        ]);
+        let solution = solve(&board, Tile::Nought).unwrap();
+        assert_eq!(solution.value, Value::Draw);
+        assert_eq!(
+            solution.moves,
+            placements(&[(0, 1), (1, 0), (1, 2), (2, 1)], Tile::Nought)
+        );
+    }
+
+    #[test]
+    fn test_forced_loss() {
+        // crosses threatens two lines at once
+        let board = Board::from([
+            [Tile::Cross, Tile::Cross, Tile::Unplayed],
+            [Tile::Cross, Tile::Nought, Tile::Unplayed],
+            [Tile::Unplayed, Tile::Unplayed, Tile::Nought],
+        ]);
+        assert_eq!(solve(&board, Tile::Nought).unwrap().value, Value::Loss);
+    }
+
+    #[test]
+    fn test_misere_empty_board_is_a_draw() {
+        let solution = Solver::with_rules(Rules::Misere)
+            .solve(&Board::new(), Tile::Cross)
+            .unwrap();
+        assert_eq!(solution.value, Value::Draw);
+        // the only opening move that holds the draw is the centre
+        assert_eq!(solution.moves, placements(&[(1, 1)], Tile::Cross));
+    }
+
+    #[test]
+    fn test_wild() {
+        // noughts can finish the line of crosses, so noughts wins
+        let board = Board::from([
+            [Tile::Cross, Tile::Cross, Tile::Unplayed],
+            [Tile::Unplayed; 3],
+            [Tile::Unplayed; 3],
+        ]);
+        let mut solver = Solver::with_rules(Rules::Wild);
+        let solution = solver.solve(&board, Tile::Nought).unwrap();
+        assert_eq!(solution.value, Value::Win);
+        assert!(solution
+            .moves
+            .contains(&((0, 2).try_into().unwrap(), Tile::Cross)));
+
+        // the first player wins wild tic-tac-toe
+        let solution = solver.solve(&Board::new(), Tile::Cross).unwrap();
+        assert_eq!(solution.value, Value::Win);
+    }
+
+    #[test]
+    fn test_finished_board_has_no_moves() {
+        let board = Board::from([[Tile::Cross; 3], [Tile::Nought; 3], [Tile::Unplayed; 3]]);
+        let solution = solve(&board, Tile::Nought).unwrap();
+        assert_eq!(solution.value, Value::Loss);
+        assert_eq!(solution.moves, []);
+    }
+}
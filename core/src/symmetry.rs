@@ -0,0 +1,213 @@
+use crate::{game_state::Position, Board, Tile};
+
+/// the eight ways a board can be rotated or reflected. Rotations are clockwise, looking down on the
+/// board with x to the right and y down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// mirrors the columns
+    FlipX,
+    /// mirrors the rows
+    FlipY,
+    /// swaps x and y
+    Transpose,
+    /// reflects in the other diagonal
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::FlipX,
+        Self::FlipY,
+        Self::Transpose,
+        Self::AntiTranspose,
+    ];
+
+    /// the transform that undoes this one
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            s => s,
+        }
+    }
+
+    /// whether the width and height are swapped
+    pub const fn swaps_sides(self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90 | Self::Rotate270 | Self::Transpose | Self::AntiTranspose
+        )
+    }
+
+    /// where `(x, y)` moves to on a board of the given width and height
+    const fn apply(self, (x, y): (usize, usize), width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Self::Identity => (x, y),
+            Self::Rotate90 => (height - 1 - y, x),
+            Self::Rotate180 => (width - 1 - x, height - 1 - y),
+            Self::Rotate270 => (y, width - 1 - x),
+            Self::FlipX => (width - 1 - x, y),
+            Self::FlipY => (x, height - 1 - y),
+            Self::Transpose => (y, x),
+            Self::AntiTranspose => (height - 1 - y, width - 1 - x),
+        }
+    }
+}
+
+impl Position {
+    /// where the position moves to when a board of the given width and height is transformed. The
+    /// position must be on the board.
+    #[must_use]
+    pub const fn transform(self, symmetry: Symmetry, width: usize, height: usize) -> Self {
+        debug_assert!(self.x() < width && self.y() < height);
+        let (x, y) = symmetry.apply((self.x(), self.y()), width, height);
+        // the transformed position is on the transformed board, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        Self::new(x as u8, y as u8)
+    }
+}
+
+impl Board {
+    /// the board rotated or reflected
+    #[must_use]
+    pub fn transform(&self, symmetry: Symmetry) -> Self {
+        let (width, height) = (self.width(), self.height());
+        let mut board = if symmetry.swaps_sides() {
+            Self {
+                tiles: vec![vec![Tile::Unplayed; width]; height],
+                win_length: self.win_length,
+            }
+        } else {
+            self.clone()
+        };
+
+        for pos in self.positions() {
+            let to = pos.transform(symmetry, width, height);
+            board.tiles[to.x()][to.y()] = self.tiles[pos.x()][pos.y()];
+        }
+        board
+    }
+
+    /// the same position for every rotation and reflection of the board, and the transform that
+    /// turns this board into it
+    pub fn canonical(&self) -> (Self, Symmetry) {
+        let mut canonical = (self.clone(), Symmetry::Identity);
+        let mut canonical_key = self.key();
+        for symmetry in Symmetry::ALL {
+            let board = self.transform(symmetry);
+            let key = board.key();
+            if key < canonical_key {
+                canonical = (board, symmetry);
+                canonical_key = key;
+            }
+        }
+        canonical
+    }
+
+    /// a hash of the canonical position, which is the same for every rotation and reflection. It
+    /// doesn't change between builds, so it can be stored.
+    pub fn canonical_hash(&self) -> u64 {
+        // 64 bit FNV-1a
+        self.canonical()
+            .0
+            .key()
+            .into_iter()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+
+    /// the size and every tile as bytes, for ordering and hashing boards
+    fn key(&self) -> Vec<u8> {
+        // the board size is limited, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        let size = [self.width(), self.height(), self.win_length].map(|n| n as u8);
+        size.into_iter()
+            .chain(self.tiles.iter().flatten().map(|tile| match tile {
+                Tile::Unplayed => 0,
+                Tile::Nought => 1,
+                Tile::Cross => 2,
+            }))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{testing::reachable, BoardSize};
+
+    #[test]
+    fn test_765_essentially_different_positions() {
+        let mut seen = HashSet::new();
+        reachable(&Board::new(), Tile::Cross, &mut seen);
+        assert_eq!(seen.len(), 5478);
+
+        let canonical: HashSet<Board> = seen.iter().map(|board| board.canonical().0).collect();
+        assert_eq!(canonical.len(), 765);
+
+        let hashes: HashSet<u64> = seen.iter().map(Board::canonical_hash).collect();
+        assert_eq!(hashes.len(), 765);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let board: Board = "XO./.../...".parse().unwrap();
+        assert_eq!(
+            board.transform(Symmetry::Rotate90).to_string(),
+            "..X/..O/..."
+        );
+        assert_eq!(
+            board.transform(Symmetry::AntiTranspose).to_string(),
+            ".../..O/..X"
+        );
+
+        let mut rotated = board.clone();
+        for _ in 0..4 {
+            rotated = rotated.transform(Symmetry::Rotate90);
+        }
+        assert_eq!(rotated, board);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let board = Board::with_size(BoardSize::new(4, 2, 3).unwrap());
+        let pos: Position = (3, 0).try_into().unwrap();
+        for symmetry in Symmetry::ALL {
+            let transformed = board.transform(symmetry);
+            let to = pos.transform(symmetry, board.width(), board.height());
+            assert!(transformed.get(to).is_some());
+            assert_eq!(
+                to.transform(
+                    symmetry.inverse(),
+                    transformed.width(),
+                    transformed.height()
+                ),
+                pos
+            );
+        }
+    }
+
+    #[test]
+    fn test_canonical_is_shared() {
+        let board: Board = "X../.O./...".parse().unwrap();
+        let (canonical, symmetry) = board.canonical();
+        assert_eq!(board.transform(symmetry), canonical);
+        for symmetry in Symmetry::ALL {
+            let transformed = board.transform(symmetry);
+            assert_eq!(transformed.canonical().0, canonical);
+            assert_eq!(transformed.canonical_hash(), board.canonical_hash());
+        }
+    }
+}
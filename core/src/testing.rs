@@ -0,0 +1,22 @@
+//! helpers shared by the tests of several modules
+
+use std::collections::HashSet;
+
+use crate::{game_state::Position, Board, Tile};
+
+/// every position reachable from the board, found by playing every legal move
+pub fn reachable(board: &Board, to_move: Tile, seen: &mut HashSet<Board>) {
+    if !seen.insert(board.clone()) {
+        return;
+    }
+    for pos in board.legal_moves() {
+        let mut next = board.clone();
+        next.play(pos, to_move).unwrap();
+        reachable(&next, to_move.opponent(), seen);
+    }
+}
+
+/// the position at `(x, y)`, which must be on a board
+pub fn pos(x: usize, y: usize) -> Position {
+    (x, y).try_into().unwrap()
+}
@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game_state::Position, Board, GameSummary, MoveError, Tile};
+
+/// a 3x3 grid of small boards. Winning a small board claims that square of the large board, and
+/// the square played in a small board sends the opponent to the matching small board.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct UltimateBoard {
+    /// indexed by x then y
+    pub boards: [[Board; 3]; 3],
+    /// the small board the next move must be played in, or none if any board can be played
+    pub active: Option<Position>,
+}
+
+impl UltimateBoard {
+    /// the width and height of the board counting every tile of the small boards
+    pub const SIZE: usize = 9;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        for board in self.boards.iter_mut().flatten() {
+            board.clear();
+        }
+        self.active = None;
+    }
+
+    /// the position of the small board and the position within it
+    pub const fn split(pos: Position) -> (Position, Position) {
+        // positions on the board are less than 9, so truncation can't happen
+        #[allow(clippy::cast_possible_truncation)]
+        (
+            Position::new((pos.x() / 3) as u8, (pos.y() / 3) as u8),
+            Position::new((pos.x() % 3) as u8, (pos.y() % 3) as u8),
+        )
+    }
+
+    /// the small board at the position on the large board
+    pub const fn board(&self, pos: Position) -> &Board {
+        &self.boards[pos.x()][pos.y()]
+    }
+
+    /// the large board, with each square taken by whoever won the small board
+    pub fn outer(&self) -> Board {
+        Board::from(self.boards.each_ref().map(|row| {
+            row.each_ref()
+                .map(|board| board.summary().winner().unwrap_or_default())
+        }))
+    }
+
+    pub fn summary(&self) -> GameSummary {
+        self.summary_with_line().0
+    }
+
+    /// the summary, and every tile of the small boards that won the game if it has been won
+    pub fn summary_with_line(&self) -> (GameSummary, Option<Vec<Position>>) {
+        match self.outer().summary_with_line() {
+            (summary, Some(line)) => {
+                let tiles = line
+                    .into_iter()
+                    .flat_map(|outer| {
+                        // small boards are 3x3, so truncation can't happen
+                        #[allow(clippy::cast_possible_truncation)]
+                        self.board(outer).positions().map(move |inner| {
+                            Position::new(
+                                (outer.x() * 3 + inner.x()) as u8,
+                                (outer.y() * 3 + inner.y()) as u8,
+                            )
+                        })
+                    })
+                    .collect();
+                (summary, Some(tiles))
+            }
+            // small boards that are tied belong to nobody, so the game is only tied once every
+            // small board is finished
+            _ if self
+                .boards
+                .iter()
+                .flatten()
+                .all(|board| board.summary().is_finished()) =>
+            {
+                (GameSummary::Tie, None)
+            }
+            _ => (GameSummary::InProgress, None),
+        }
+    }
+
+    /// every tile of the small boards laid out on a single 9x9 board
+    pub fn tiles(&self) -> Board {
+        let mut tiles = Board {
+            tiles: vec![vec![Tile::Unplayed; Self::SIZE]; Self::SIZE],
+            win_length: 3,
+        };
+        for pos in tiles.clone().positions() {
+            let (outer, inner) = Self::split(pos);
+            tiles.tiles[pos.x()][pos.y()] = self.board(outer).get(inner).unwrap_or_default();
+        }
+        tiles
+    }
+
+    /// checks the position can be played: the game and the small board are in progress, the small
+    /// board is the one the player was sent to, and the tile is unplayed
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the move breaks.
+    pub fn check_move(&self, pos: Position) -> Result<(), MoveError> {
+        if self.summary().is_finished() {
+            return Err(MoveError::GameOver);
+        }
+        if pos.x() >= Self::SIZE || pos.y() >= Self::SIZE {
+            return Err(MoveError::OutOfBounds);
+        }
+
+        let (outer, inner) = Self::split(pos);
+        if self.active.is_some_and(|active| active != outer) {
+            return Err(MoveError::WrongBoard);
+        }
+        self.board(outer).check_move(inner).map_err(|e| match e {
+            // a finished small board can't be sent to, so it can only be chosen by mistake
+            MoveError::GameOver => MoveError::WrongBoard,
+            e => e,
+        })
+    }
+
+    pub fn is_legal(&self, pos: Position) -> bool {
+        self.check_move(pos).is_ok()
+    }
+
+    /// every position that can be played
+    pub fn legal_moves(&self) -> Vec<Position> {
+        self.tiles()
+            .positions()
+            .filter(|&pos| self.is_legal(pos))
+            .collect()
+    }
+
+    /// plays the tile, and sends the opponent to the small board matching the position played.
+    /// If that small board is finished, the opponent can play anywhere.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move isn't legal. The board is left unchanged.
+    pub fn play(&mut self, pos: Position, tile: Tile) -> Result<(), MoveError> {
+        self.check_move(pos)?;
+        let (outer, inner) = Self::split(pos);
+        self.boards[outer.x()][outer.y()].play(inner, tile)?;
+
+        self.active = if self.board(inner).summary().is_finished() {
+            None
+        } else {
+            Some(inner)
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pos;
+
+    #[test]
+    fn test_sends_opponent_to_matching_board() {
+        let mut board = UltimateBoard::new();
+        board.play(pos(4, 2), Tile::Cross).unwrap();
+        assert_eq!(board.active, Some(pos(1, 2)));
+        assert!(board.is_legal(pos(3, 6)));
+        assert_eq!(board.check_move(pos(0, 0)), Err(MoveError::WrongBoard));
+        assert_eq!(board.tiles().get(pos(4, 2)), Some(Tile::Cross));
+    }
+
+    #[test]
+    fn test_finished_board_frees_choice() {
+        let mut board = UltimateBoard::new();
+        board.boards[0][0] =
+            Board::from([[Tile::Nought; 3], [Tile::Unplayed; 3], [Tile::Unplayed; 3]]);
+        board.play(pos(3, 3), Tile::Cross).unwrap();
+        assert_eq!(board.active, None);
+        assert_eq!(board.check_move(pos(2, 2)), Err(MoveError::WrongBoard));
+        assert_eq!(board.check_move(pos(3, 3)), Err(MoveError::Occupied));
+        assert!(board.is_legal(pos(8, 8)));
+    }
+
+    #[test]
+    fn test_three_small_boards_in_a_row_wins() {
+        let mut board = UltimateBoard::new();
+        for y in 0..3 {
+            board.boards[1][y] =
+                Board::from([[Tile::Cross; 3], [Tile::Unplayed; 3], [Tile::Unplayed; 3]]);
+        }
+        let (summary, line) = board.summary_with_line();
+        assert_eq!(summary, GameSummary::CrossWin);
+        let line = line.unwrap();
+        assert_eq!(line.len(), 27);
+        assert!(line.contains(&pos(3, 0)));
+        assert!(line.contains(&pos(5, 8)));
+        assert_eq!(board.check_move(pos(0, 0)), Err(MoveError::GameOver));
+        assert_eq!(board.legal_moves(), []);
+    }
+
+    #[test]
+    fn test_tied_small_boards_belong_to_nobody() {
+        let tie = Board::from([
+            [Tile::Nought, Tile::Cross, Tile::Nought],
+            [Tile::Nought, Tile::Cross, Tile::Nought],
+            [Tile::Cross, Tile::Nought, Tile::Cross],
+        ]);
+        let mut board = UltimateBoard::new();
+        board.boards = [
+            [tie.clone(), tie.clone(), tie.clone()],
+            [tie.clone(), tie.clone(), tie.clone()],
+            [tie.clone(), tie.clone(), tie],
+        ];
+        assert_eq!(board.outer(), Board::new());
+        assert_eq!(board.summary(), GameSummary::Tie);
+    }
+}
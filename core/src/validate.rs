@@ -0,0 +1,718 @@
+use thiserror::Error;
+
+use crate::{
+    gobblet::GobbletBoard, gomoku::GomokuBoard, infinite::InfiniteBoard, notakto::NotaktoBoard,
+    numerical::NumericalBoard, order_chaos::OrderChaosBoard, quantum::QuantumBoard,
+    qubic::QubicBoard, ultimate::UltimateBoard, Board, BoardSize, Rules, Tile,
+};
+
+/// why a board can't have been reached by playing the game
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidBoard {
+    #[error("the board is not a valid size")]
+    Size,
+    #[error("one side has played more than one extra piece")]
+    PieceCount,
+    #[error("both sides have won")]
+    BothWon,
+    #[error("a piece was played after the game was won")]
+    PlayedAfterWin,
+    #[error("the active small board is finished")]
+    InactiveBoard,
+    #[error("a symbol was played that the mode doesn't allow")]
+    Symbol,
+    #[error("the winning line doesn't match the board")]
+    WinningLine,
+    #[error("a piece is floating above an unplayed tile")]
+    Floating,
+    #[error("the quantum marks haven't collapsed consistently")]
+    Collapse,
+    #[error("the pieces on the board don't match the order they were played in")]
+    Queue,
+    #[error("a piece is stacked on one that isn't smaller")]
+    Stack,
+    #[error("order must be played by one of the seats")]
+    Roles,
+    #[error("a number is out of range or has been played more than once")]
+    Number,
+}
+
+impl Board {
+    fn count(&self, tile: Tile) -> usize {
+        self.tiles.iter().flatten().filter(|&&t| t == tile).count()
+    }
+
+    /// checks the board could have been reached by playing the game. Either side may have started.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), InvalidBoard> {
+        self.validate_with(Rules::Normal)
+    }
+
+    /// checks the board could have been reached by playing with the rules. Either side may have
+    /// started. In wild games either symbol may be placed by either side, so the pieces can't be
+    /// counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate_with(&self, rules: Rules) -> Result<(), InvalidBoard> {
+        self.validate_shape()?;
+        if rules == Rules::Wild {
+            return self.validate_win().map(|_| ());
+        }
+
+        let (crosses, noughts) = (self.count(Tile::Cross), self.count(Tile::Nought));
+        if crosses.abs_diff(noughts) > 1 {
+            return Err(InvalidBoard::PieceCount);
+        }
+
+        let Some(winner) = self.validate_win()? else {
+            return Ok(());
+        };
+
+        // the winner moved last, so the loser can't have played more pieces
+        if self.count(winner) < self.count(winner.opponent()) {
+            return Err(InvalidBoard::PlayedAfterWin);
+        }
+
+        Ok(())
+    }
+
+    /// checks the board could have been reached by dropping pieces, so none are floating
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate_gravity(&self, rules: Rules) -> Result<(), InvalidBoard> {
+        self.validate_with(rules)?;
+        let floating = self.tiles.iter().any(|column| {
+            column
+                .windows(2)
+                .any(|pair| !pair[0].is_unplayed() && pair[1].is_unplayed())
+        });
+        if floating {
+            return Err(InvalidBoard::Floating);
+        }
+        Ok(())
+    }
+
+    /// checks the tiles are a valid size for the win length
+    fn validate_shape(&self) -> Result<(), InvalidBoard> {
+        let height = self.height();
+        if self.tiles.iter().any(|column| column.len() != height)
+            || BoardSize::new(self.width(), height, self.win_length).is_err()
+        {
+            return Err(InvalidBoard::Size);
+        }
+        Ok(())
+    }
+
+    /// checks at most one side has won, and that a single move won the game. Returns the winner.
+    fn validate_win(&self) -> Result<Option<Tile>, InvalidBoard> {
+        let wins = |board: &Self, tile: Tile| {
+            board.positions().any(|start| {
+                Self::DIRECTIONS
+                    .iter()
+                    .any(|&dir| board.line(start, dir, tile).is_some())
+            })
+        };
+
+        let winner = match (wins(self, Tile::Cross), wins(self, Tile::Nought)) {
+            (true, true) => return Err(InvalidBoard::BothWon),
+            (true, false) => Tile::Cross,
+            (false, true) => Tile::Nought,
+            (false, false) => return Ok(None),
+        };
+
+        // every winning line must pass through the winning move, so taking it back leaves no win
+        let last_move = self.positions().any(|pos| {
+            if self.get(pos) != Some(winner) {
+                return false;
+            }
+            let mut before = self.clone();
+            before.tiles[pos.x()][pos.y()] = Tile::Unplayed;
+            !wins(&before, winner)
+        });
+        if !last_move {
+            return Err(InvalidBoard::PlayedAfterWin);
+        }
+
+        Ok(Some(winner))
+    }
+}
+
+impl UltimateBoard {
+    /// checks the board could have been reached by playing the game. Either side may have started.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), InvalidBoard> {
+        self.validate_with(Rules::Normal)
+    }
+
+    /// checks the board could have been reached by playing with the rules. Either side may have
+    /// started.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate_with(&self, rules: Rules) -> Result<(), InvalidBoard> {
+        for board in self.boards.iter().flatten() {
+            board.validate_shape()?;
+            if board.size() != BoardSize::default() {
+                return Err(InvalidBoard::Size);
+            }
+            // small boards are played in any order, so only the wins can be checked
+            board.validate_win()?;
+        }
+
+        let count = |tile| {
+            self.boards
+                .iter()
+                .flatten()
+                .map(|b| b.count(tile))
+                .sum::<usize>()
+        };
+        if rules != Rules::Wild && count(Tile::Cross).abs_diff(count(Tile::Nought)) > 1 {
+            return Err(InvalidBoard::PieceCount);
+        }
+
+        if let Some(active) = self.active {
+            if active.x() >= 3 || active.y() >= 3 || self.board(active).summary().is_finished() {
+                return Err(InvalidBoard::InactiveBoard);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NotaktoBoard {
+    /// checks the board could have been reached by playing the game
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), InvalidBoard> {
+        if self.boards.is_empty() || self.boards.len() * Self::SIZE > 16 {
+            return Err(InvalidBoard::Size);
+        }
+        for board in &self.boards {
+            board.validate_shape()?;
+            if board.size() != BoardSize::default() {
+                return Err(InvalidBoard::Size);
+            }
+            if board.count(Tile::Nought) > 0 {
+                return Err(InvalidBoard::Symbol);
+            }
+            // a board can't be played once it has a line
+            board.validate_win()?;
+        }
+        Ok(())
+    }
+}
+
+impl GomokuBoard {
+    /// checks the board could have been reached by playing with the rules, and that the winning
+    /// line kept with it is right
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate_with(&self, rules: Rules) -> Result<(), InvalidBoard> {
+        if self.board.size() != Self::SIZE {
+            return Err(InvalidBoard::Size);
+        }
+        self.board.validate_with(rules)?;
+
+        let won = self.board.summary().winner().is_some();
+        match &self.winning_line {
+            None if won => Err(InvalidBoard::WinningLine),
+            None => Ok(()),
+            Some(line) => {
+                let tile = line.first().and_then(|&pos| self.board.get(pos));
+                if won
+                    && line.len() >= Self::SIZE.win_length()
+                    && tile.is_some_and(|tile| !tile.is_unplayed())
+                    && line.iter().all(|&pos| self.board.get(pos) == tile)
+                {
+                    Ok(())
+                } else {
+                    Err(InvalidBoard::WinningLine)
+                }
+            }
+        }
+    }
+}
+
+impl QubicBoard {
+    /// checks the cube could have been reached by playing with the rules. Either side may have
+    /// started.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate_with(&self, rules: Rules) -> Result<(), InvalidBoard> {
+        if self.layers.len() != Self::SIZE {
+            return Err(InvalidBoard::Size);
+        }
+        for layer in &self.layers {
+            layer.validate_shape()?;
+            if layer.size() != Self::LAYER {
+                return Err(InvalidBoard::Size);
+            }
+        }
+
+        let count = |tile| self.layers.iter().map(|l| l.count(tile)).sum::<usize>();
+        if rules != Rules::Wild && count(Tile::Cross).abs_diff(count(Tile::Nought)) > 1 {
+            return Err(InvalidBoard::PieceCount);
+        }
+
+        let wins = |cube: &Self, tile| {
+            Self::lines()
+                .iter()
+                .any(|line| cube.winner(line) == Some(tile))
+        };
+        let winner = match (wins(self, Tile::Cross), wins(self, Tile::Nought)) {
+            (true, true) => return Err(InvalidBoard::BothWon),
+            (true, false) => Tile::Cross,
+            (false, true) => Tile::Nought,
+            (false, false) => return Ok(()),
+        };
+
+        // every winning line must pass through the winning move, so taking it back leaves no win
+        let last_move = self.tiles().positions().any(|pos| {
+            if self.get(pos) != Some(winner) {
+                return false;
+            }
+            let mut before = self.clone();
+            let (i, inner) = Self::split(pos);
+            before.layers[i].tiles[inner.x()][inner.y()] = Tile::Unplayed;
+            !wins(&before, winner)
+        });
+        // the winner moved last, so the loser can't have played more pieces
+        if !last_move || (rules != Rules::Wild && count(winner) < count(winner.opponent())) {
+            return Err(InvalidBoard::PlayedAfterWin);
+        }
+
+        Ok(())
+    }
+}
+
+impl InfiniteBoard {
+    /// checks the board could have been reached by playing with the rules, and that the queue
+    /// holds every piece on the board once
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate_with(&self, rules: Rules) -> Result<(), InvalidBoard> {
+        if self.board.size() != BoardSize::default() {
+            return Err(InvalidBoard::Size);
+        }
+        self.board.validate_with(rules)?;
+
+        let played = self
+            .board
+            .positions()
+            .filter(|&pos| self.board.get(pos).is_some_and(|tile| !tile.is_unplayed()));
+        let mut queued = self.queue.clone();
+        queued
+            .make_contiguous()
+            .sort_unstable_by_key(|pos| (pos.x(), pos.y()));
+        if !queued.iter().copied().eq(played) {
+            return Err(InvalidBoard::Queue);
+        }
+
+        if [Tile::Cross, Tile::Nought]
+            .into_iter()
+            .any(|tile| self.board.count(tile) > Self::PIECES)
+        {
+            return Err(InvalidBoard::PieceCount);
+        }
+        Ok(())
+    }
+}
+
+impl NumericalBoard {
+    /// checks every number on the board is played once, and the odd numbers have been played
+    /// first
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), InvalidBoard> {
+        if self.cells.len() != 3 || self.cells.iter().any(|column| column.len() != 3) {
+            return Err(InvalidBoard::Size);
+        }
+        if self.odd.is_unplayed() {
+            return Err(InvalidBoard::Roles);
+        }
+        let numbers: Vec<u8> = self.cells.iter().flatten().copied().flatten().collect();
+        if numbers
+            .iter()
+            .enumerate()
+            .any(|(i, number)| !Self::NUMBERS.contains(number) || numbers[..i].contains(number))
+        {
+            return Err(InvalidBoard::Number);
+        }
+        let odd = numbers.iter().filter(|&&number| number % 2 == 1).count();
+        let even = numbers.len() - odd;
+        if odd < even || odd > even + 1 {
+            return Err(InvalidBoard::PieceCount);
+        }
+        Ok(())
+    }
+}
+
+impl OrderChaosBoard {
+    /// checks the board could have been reached by playing the game. Either seat places either
+    /// symbol, so the pieces can't be counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), InvalidBoard> {
+        if self.board.size() != Self::SIZE {
+            return Err(InvalidBoard::Size);
+        }
+        if self.order.is_unplayed() {
+            return Err(InvalidBoard::Roles);
+        }
+        self.board.validate_with(Rules::Wild)
+    }
+}
+
+impl GobbletBoard {
+    /// checks every stack gets larger towards the top, and neither player has more pieces than
+    /// they started with. Moving pieces around the board doesn't change the count, so the sides
+    /// can be any number of pieces apart.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), InvalidBoard> {
+        if self.stacks.len() != 3 || self.stacks.iter().any(|column| column.len() != 3) {
+            return Err(InvalidBoard::Size);
+        }
+        let pieces = || self.stacks.iter().flatten().flatten();
+        if pieces().any(|piece| piece.tile.is_unplayed()) {
+            return Err(InvalidBoard::Symbol);
+        }
+        if self
+            .stacks
+            .iter()
+            .flatten()
+            .any(|stack| stack.windows(2).any(|pair| pair[0].size >= pair[1].size))
+        {
+            return Err(InvalidBoard::Stack);
+        }
+        if pieces().any(|&piece| pieces().filter(|&&p| p == piece).count() > Self::PIECES) {
+            return Err(InvalidBoard::PieceCount);
+        }
+        // a move can only leave both players with a line by uncovering one
+        if self.last_mover.is_none()
+            && self.line(Tile::Cross).is_some()
+            && self.line(Tile::Nought).is_some()
+        {
+            return Err(InvalidBoard::BothWon);
+        }
+        Ok(())
+    }
+}
+
+impl QuantumBoard {
+    /// checks the marks could have been played with the rules, taking turns, and that every
+    /// collapse left at most one classical mark in each cell
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate_with(&self, rules: Rules) -> Result<(), InvalidBoard> {
+        if self.marks.len() > 9 {
+            return Err(InvalidBoard::PieceCount);
+        }
+        if rules != Rules::Wild
+            && self
+                .marks
+                .windows(2)
+                .any(|pair| pair[0].tile == pair[1].tile)
+        {
+            return Err(InvalidBoard::PieceCount);
+        }
+
+        for (i, mark) in self.marks.iter().enumerate() {
+            if mark.tile.is_unplayed() {
+                return Err(InvalidBoard::Symbol);
+            }
+            if mark.cells.iter().any(|cell| cell.x() >= 3 || cell.y() >= 3) {
+                return Err(InvalidBoard::Size);
+            }
+            // a mark is only played in the same cell twice when it is classical straight away
+            let consistent = mark.collapsed.map_or_else(
+                || mark.cells[0] != mark.cells[1],
+                |cell| mark.cells.contains(&cell),
+            );
+            // spooky marks can't be played in classical cells, and no cycle can be left
+            // uncollapsed except by the last move
+            let [a, b] = mark.cells;
+            let uncollapsed = mark.collapsed.is_none()
+                && (mark
+                    .cells
+                    .iter()
+                    .any(|&cell| self.classical(cell).is_some())
+                    || (i + 1 < self.marks.len() && Self::connected(&self.marks[..i], a, b)));
+            if !consistent || uncollapsed {
+                return Err(InvalidBoard::Collapse);
+            }
+        }
+
+        let mut classical: Vec<_> = self.marks.iter().filter_map(|m| m.collapsed).collect();
+        classical.sort_unstable_by_key(|cell| (cell.x(), cell.y()));
+        if classical.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(InvalidBoard::Collapse);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{
+        gobblet::{GobbletMove, Size},
+        testing::{pos, reachable},
+    };
+
+    fn validate(notation: &str) -> Result<(), InvalidBoard> {
+        notation.parse::<Board>().unwrap().validate()
+    }
+
+    #[test]
+    fn test_reachable() {
+        assert_eq!(validate(".../.../..."), Ok(()));
+        assert_eq!(validate("O../.../..."), Ok(()));
+        assert_eq!(validate("XXX/OO./..."), Ok(()));
+        // crosses completes two lines with the last move
+        assert_eq!(validate("XXX/OXO/XOO"), Ok(()));
+    }
+
+    #[test]
+    fn test_every_reachable_board_is_valid() {
+        let mut seen = HashSet::new();
+        reachable(&Board::new(), Tile::Cross, &mut seen);
+        reachable(&Board::new(), Tile::Nought, &mut seen);
+        for board in seen {
+            assert_eq!(board.validate(), Ok(()), "{board}");
+        }
+    }
+
+    #[test]
+    fn test_piece_count() {
+        assert_eq!(validate("XX./.../..."), Err(InvalidBoard::PieceCount));
+    }
+
+    #[test]
+    fn test_both_won() {
+        assert_eq!(validate("XXX/OOO/..."), Err(InvalidBoard::BothWon));
+    }
+
+    #[test]
+    fn test_played_after_win() {
+        assert_eq!(validate("XXX/OO./O.O"), Err(InvalidBoard::PlayedAfterWin));
+        // two separate lines can't both be completed by one move
+        assert_eq!(
+            validate("XXX../OO.OO/...../..O../XXX.."),
+            Err(InvalidBoard::PlayedAfterWin)
+        );
+    }
+
+    #[test]
+    fn test_wild() {
+        let board: Board = "OOO/.X./...".parse().unwrap();
+        assert_eq!(board.validate(), Err(InvalidBoard::PieceCount));
+        assert_eq!(board.validate_with(Rules::Wild), Ok(()));
+        assert_eq!(
+            "XXX/OOO/..."
+                .parse::<Board>()
+                .unwrap()
+                .validate_with(Rules::Wild),
+            Err(InvalidBoard::BothWon)
+        );
+    }
+
+    #[test]
+    fn test_notakto() {
+        let mut board = NotaktoBoard::default();
+        board.boards[1] = "XXX/.../XXX".parse().unwrap();
+        assert_eq!(board.validate(), Err(InvalidBoard::PlayedAfterWin));
+        board.boards[1] = "XXX/.O./...".parse().unwrap();
+        assert_eq!(board.validate(), Err(InvalidBoard::Symbol));
+        board.boards[1] = "XXX/.../...".parse().unwrap();
+        assert_eq!(board.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_gomoku() {
+        let mut board = GomokuBoard::default();
+        for (x, tile) in [(0, Tile::Cross), (1, Tile::Nought)] {
+            for y in 0..4 {
+                board.play((x, y).try_into().unwrap(), tile).unwrap();
+            }
+        }
+        board.play((0, 4).try_into().unwrap(), Tile::Cross).unwrap();
+        assert_eq!(board.validate_with(Rules::Normal), Ok(()));
+
+        board.winning_line = None;
+        assert_eq!(
+            board.validate_with(Rules::Normal),
+            Err(InvalidBoard::WinningLine)
+        );
+    }
+
+    #[test]
+    fn test_gravity() {
+        let board: Board = ".../X../XO.".parse().unwrap();
+        assert_eq!(board.validate_gravity(Rules::Normal), Ok(()));
+        let board: Board = "X../.../.O.".parse().unwrap();
+        assert_eq!(
+            board.validate_gravity(Rules::Normal),
+            Err(InvalidBoard::Floating)
+        );
+    }
+
+    #[test]
+    fn test_qubic() {
+        let mut board = QubicBoard::default();
+        for x in 0..4 {
+            board.layers[0].tiles[x][0] = Tile::Cross;
+        }
+        for x in 0..3 {
+            board.layers[1].tiles[x][0] = Tile::Nought;
+        }
+        assert_eq!(board.validate_with(Rules::Normal), Ok(()));
+        board.layers[2].tiles[0][0] = Tile::Nought;
+        board.layers[3].tiles[0][0] = Tile::Nought;
+        assert_eq!(
+            board.validate_with(Rules::Normal),
+            Err(InvalidBoard::PlayedAfterWin)
+        );
+        board.layers.pop();
+        assert_eq!(board.validate_with(Rules::Normal), Err(InvalidBoard::Size));
+    }
+
+    #[test]
+    fn test_quantum() {
+        let mut board = QuantumBoard::default();
+        board.entangle(pos(0, 0), pos(1, 1), Tile::Cross).unwrap();
+        board.entangle(pos(1, 1), pos(0, 0), Tile::Nought).unwrap();
+        assert_eq!(board.validate_with(Rules::Normal), Ok(()));
+
+        let mut twice = board.clone();
+        twice.marks[1].tile = Tile::Cross;
+        assert_eq!(
+            twice.validate_with(Rules::Normal),
+            Err(InvalidBoard::PieceCount)
+        );
+
+        // the cycle must be collapsed before anything else is played
+        board.marks.push(board.marks[0]);
+        assert_eq!(
+            board.validate_with(Rules::Normal),
+            Err(InvalidBoard::Collapse)
+        );
+        board.marks.pop();
+        board.collapse(pos(1, 1)).unwrap();
+        board.marks[0].collapsed = Some(pos(1, 1));
+        assert_eq!(
+            board.validate_with(Rules::Normal),
+            Err(InvalidBoard::Collapse)
+        );
+    }
+
+    #[test]
+    fn test_infinite() {
+        let mut board = InfiniteBoard::default();
+        for (xy, tile) in [((0, 0), Tile::Cross), ((1, 1), Tile::Nought)] {
+            board.play(xy.try_into().unwrap(), tile).unwrap();
+        }
+        assert_eq!(board.validate_with(Rules::Normal), Ok(()));
+        board.queue.pop_front();
+        assert_eq!(board.validate_with(Rules::Normal), Err(InvalidBoard::Queue));
+
+        let mut board = InfiniteBoard {
+            board: "XXO/OO./XX.".parse().unwrap(),
+            ..Default::default()
+        };
+        board.queue = board
+            .board
+            .positions()
+            .filter(|&pos| board.board.get(pos) != Some(Tile::Unplayed))
+            .collect();
+        assert_eq!(
+            board.validate_with(Rules::Normal),
+            Err(InvalidBoard::PieceCount)
+        );
+    }
+
+    #[test]
+    fn test_gobblet() {
+        let mut board = GobbletBoard::default();
+        for (size, tile) in [(Size::Small, Tile::Cross), (Size::Large, Tile::Nought)] {
+            let m = GobbletMove {
+                size,
+                from: None,
+                to: pos(1, 1),
+            };
+            board.play(m, tile).unwrap();
+        }
+        assert_eq!(board.validate(), Ok(()));
+
+        let mut upside_down = board.clone();
+        upside_down.stacks[1][1].reverse();
+        assert_eq!(upside_down.validate(), Err(InvalidBoard::Stack));
+
+        let piece = board.stacks[1][1][1];
+        board.stacks[0][0].push(piece);
+        board.stacks[2][2].push(piece);
+        assert_eq!(board.validate(), Err(InvalidBoard::PieceCount));
+    }
+
+    #[test]
+    fn test_numerical() {
+        let mut board = NumericalBoard::default();
+        board.cells[0][0] = Some(3);
+        assert_eq!(board.validate(), Ok(()));
+        board.cells[1][1] = Some(3);
+        assert_eq!(board.validate(), Err(InvalidBoard::Number));
+        board.cells[1][1] = Some(10);
+        assert_eq!(board.validate(), Err(InvalidBoard::Number));
+        board.cells[1][1] = Some(4);
+        board.cells[2][2] = Some(6);
+        assert_eq!(board.validate(), Err(InvalidBoard::PieceCount));
+    }
+
+    #[test]
+    fn test_uneven_columns() {
+        let mut board = Board::new();
+        board.tiles[1].pop();
+        assert_eq!(board.validate(), Err(InvalidBoard::Size));
+    }
+
+    #[test]
+    fn test_ultimate() {
+        let mut board = UltimateBoard::new();
+        board.play((4, 4).try_into().unwrap(), Tile::Cross).unwrap();
+        assert_eq!(board.validate(), Ok(()));
+
+        board.boards[1][1] = "XXX/OO./...".parse().unwrap();
+        assert_eq!(board.validate(), Err(InvalidBoard::InactiveBoard));
+    }
+}
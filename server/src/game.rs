@@ -6,7 +6,7 @@ use worker::{
     Request, Response, Result, State, WebSocketPair, WebsocketEvent,
 };
 
-use noughts_and_crosses_core::messages::ServerMessage;
+use noughts_and_crosses_core::{messages::ServerMessage, options::GameOptions};
 
 use crate::{game_state::GameState, send_message};
 
@@ -14,10 +14,20 @@ use crate::{game_state::GameState, send_message};
 struct Game {
     game_state: Rc<Mutex<GameState>>,
 
-    // This durable object doesn't actually store any state. Re-connecting isn't allowed.
-    // If the durable object restarts, the game will end.
-    #[allow(dead_code)]
-    state: State,
+    // The game state is saved to storage so a game survives the durable object restarting.
+    state: Rc<State>,
+    loaded: bool,
+}
+
+impl Game {
+    /// storage can't be read in new, so the game is restored before handling the first event
+    async fn restore(&mut self) {
+        if !self.loaded {
+            let game_state = GameState::load(self.state.clone()).await;
+            *self.game_state.lock().unwrap() = game_state;
+            self.loaded = true;
+        }
+    }
 }
 
 #[durable_object]
@@ -25,12 +35,21 @@ impl DurableObject for Game {
     fn new(state: State, _: Env) -> Self {
         Self {
             game_state: Rc::new(Mutex::new(GameState::default())),
-            state,
+            state: Rc::new(state),
+            loaded: false,
         }
     }
 
-    #[allow(clippy::unused_async)] // must be async to satisfy the async trait
-    async fn fetch(&mut self, _req: Request) -> Result<Response> {
+    async fn fetch(&mut self, req: Request) -> Result<Response> {
+        self.restore().await;
+
+        // options for a new game are passed in the query string
+        let url = req.url()?;
+        let options = match GameOptions::try_from(url.query().unwrap_or_default()) {
+            Ok(options) => options,
+            Err(e) => return Response::error(e.to_string(), 400),
+        };
+
         let WebSocketPair { client, server } = WebSocketPair::new()?;
 
         let session = Rc::new(server);
@@ -38,17 +57,8 @@ impl DurableObject for Game {
 
         session.accept()?;
 
+        // the client joins or reclaims a seat with its first message
         wasm_bindgen_futures::spawn_local(async move {
-            {
-                let mut game_state = game_state.lock().unwrap();
-                if let Err(e) = game_state.join(&session) {
-                    drop(game_state);
-                    send_message(&ServerMessage::Error(e.to_string()), &session)
-                        .expect("send error to client");
-                    return;
-                };
-            }
-
             let events = session.events();
             if let Ok(mut stream) = events {
                 while let Some(Ok(event)) = stream.next().await {
@@ -57,10 +67,26 @@ impl DurableObject for Game {
                             if let Some(bytes) = msg.bytes() {
                                 let mut game_state = game_state.lock().unwrap();
 
-                                if let Err(e) = game_state.handle_message(&session, &bytes) {
+                                // a client that can't speak the protocol is no longer listened to,
+                                // so it leaves the game
+                                let Ok(message) = bincode::deserialize(&bytes) else {
+                                    send_message(
+                                        &ServerMessage::Error(
+                                            "invalid message from client".to_string(),
+                                        ),
+                                        &session,
+                                    )
+                                    .expect("send error to client");
+                                    game_state.player_left(&session).expect("closing game");
+                                    return;
+                                };
+
+                                // a rejected move or request is reported, and the session carries on
+                                if let Err(e) =
+                                    game_state.handle_message(&session, message, &options)
+                                {
                                     send_message(&ServerMessage::Error(e.to_string()), &session)
                                         .expect("send error to client");
-                                    return;
                                 }
                             }
                         }
@@ -75,4 +101,11 @@ impl DurableObject for Game {
 
         Response::from_websocket(client)
     }
+
+    /// fires when a disconnected player's seat is due to expire
+    async fn alarm(&mut self) -> Result<Response> {
+        self.restore().await;
+        self.game_state.lock().unwrap().expire_seats()?;
+        Response::ok("")
+    }
 }
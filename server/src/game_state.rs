@@ -1,56 +1,421 @@
-use std::rc::Rc;
+use std::{rc::Rc, time::Duration};
 
 use rand::Rng;
-use worker::{Error, Result, WebSocket};
+use serde::{de::IgnoredAny, Deserialize, Deserializer, Serialize};
+use worker::{console_error, wasm_bindgen_futures, Date, Error, Result, State, WebSocket};
 
 use noughts_and_crosses_core::{
-    messages::{ClientMessage, GameUpdate, ServerMessage},
-    Board, GameSummary, Tile,
+    bot::Difficulty,
+    game::{Game, GameBoard},
+    game_state::SeatToken,
+    messages::{ClientMessage, GameUpdate, SeatAssignment, ServerMessage},
+    options::GameOptions,
+    quantum::QuantumMove,
+    record::{GameRecord, Play},
+    MoveError, Tile,
 };
 
 use crate::send_message;
 
-#[derive(Debug, Clone, Default)]
+/// the key the game state is saved under in durable storage
+const STORAGE_KEY: &str = "game_state";
+
+/// the error durable storage gives when nothing has been saved under a key
+const MISSING_KEY: &str = "No such value in storage.";
+
+/// how long a disconnected player has to reclaim their seat before the game ends
+const RECONNECT_GRACE_PERIOD_MS: u64 = 30_000;
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Seat {
+    token: SeatToken,
+    // the seat is played by the computer
+    #[serde(default)]
+    computer: Option<Difficulty>,
+    // sessions can't be stored. After a restart the players reclaim their seats to resume the game.
+    #[serde(skip)]
+    session: Option<Rc<WebSocket>>,
+    // when the player disconnected, in milliseconds since the epoch
+    #[serde(skip)]
+    disconnected_at: u64,
+}
+
+impl Seat {
+    fn new(session: &Rc<WebSocket>) -> Self {
+        Self {
+            token: SeatToken::new(),
+            computer: None,
+            session: Some(session.clone()),
+            disconnected_at: 0,
+        }
+    }
+
+    fn computer(difficulty: Difficulty) -> Self {
+        Self {
+            token: SeatToken::new(),
+            computer: Some(difficulty),
+            session: None,
+            disconnected_at: 0,
+        }
+    }
+
+    /// whether the seat is held by the session
+    fn is(&self, session: &Rc<WebSocket>) -> bool {
+        self.session.as_ref().is_some_and(|s| s == session)
+    }
+
+    /// when the seat is given up if the player doesn't reconnect
+    const fn expires_at(&self) -> Option<u64> {
+        if self.session.is_some() || self.computer.is_some() {
+            None
+        } else {
+            Some(self.disconnected_at + RECONNECT_GRACE_PERIOD_MS)
+        }
+    }
+}
+
+/// the saved record, or an empty one if it was saved before the way moves are recorded changed.
+/// The game itself carries on either way.
+fn record_or_default<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<GameRecord, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Saved {
+        Record(GameRecord),
+        Outdated(IgnoredAny),
+    }
+
+    Ok(match Saved::deserialize(deserializer)? {
+        Saved::Record(record) => record,
+        Saved::Outdated(_) => GameRecord::default(),
+    })
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct GameState {
-    board: Board,
-    crosses_session: Option<Rc<WebSocket>>,
-    noughts_session: Option<Rc<WebSocket>>,
+    // fields added since games were first saved default when restoring an older save
+    #[serde(default)]
+    options: GameOptions,
+    #[serde(default)]
+    game: Game,
+    // every move of the current game
+    #[serde(default, deserialize_with = "record_or_default")]
+    record: GameRecord,
+    #[serde(default)]
+    crosses: Option<Seat>,
+    #[serde(default)]
+    noughts: Option<Seat>,
+    #[serde(skip)]
+    spectators: Vec<Rc<WebSocket>>,
 
     crosses_turn: bool,
 
     crosses_requests_rematch: bool,
     noughts_requests_rematch: bool,
+
+    // the durable object state used to save the game
+    #[serde(skip)]
+    state: Option<Rc<State>>,
 }
 
 impl GameState {
-    /// request to join a game
-    pub fn join(&mut self, session: &Rc<WebSocket>) -> Result<()> {
+    /// restores the game from storage, or starts a fresh one if nothing has been saved
+    pub async fn load(state: Rc<State>) -> Self {
+        let mut game_state = match Self::restore(&state).await {
+            Ok(game_state) => game_state.unwrap_or_default(),
+            Err(e) => {
+                console_error!("failed to restore game: {e}");
+                Self::default()
+            }
+        };
+        if let Err(e) = game_state.game.validate() {
+            console_error!("discarding saved game: {e}");
+            game_state = Self::default();
+        }
+        game_state.state = Some(state);
+
+        // nobody is connected after a restart. Give the players a chance to reclaim their seats.
+        let now = Date::now().as_millis();
+        for seat in [&mut game_state.crosses, &mut game_state.noughts]
+            .into_iter()
+            .flatten()
+        {
+            seat.disconnected_at = now;
+        }
+        game_state.schedule_expiry();
+
+        game_state
+    }
+
+    /// reads the saved game, if there is one. Errors if storage fails or the save no longer
+    /// deserializes.
+    async fn restore(state: &State) -> Result<Option<Self>> {
+        match state.storage().get(STORAGE_KEY).await {
+            Ok(game_state) => Ok(Some(game_state)),
+            Err(Error::JsError(e)) if e == MISSING_KEY => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// saves the game to storage in the background
+    fn save(&self) {
+        if let Some(state) = &self.state {
+            let mut storage = state.storage();
+            let game_state = self.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(e) = storage.put(STORAGE_KEY, game_state).await {
+                    console_error!("failed to save game: {e}");
+                }
+            });
+        }
+    }
+
+    /// removes the game from storage in the background
+    fn delete(&self) {
+        if let Some(state) = &self.state {
+            let mut storage = state.storage();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(e) = storage.delete(STORAGE_KEY).await {
+                    console_error!("failed to delete game: {e}");
+                }
+            });
+        }
+    }
+
+    /// sets an alarm for when the next disconnected player's seat expires
+    fn schedule_expiry(&self) {
+        let Some(expires_at) = [&self.crosses, &self.noughts]
+            .into_iter()
+            .flatten()
+            .filter_map(Seat::expires_at)
+            .min()
+        else {
+            return;
+        };
+
+        if let Some(state) = &self.state {
+            let storage = state.storage();
+            let delay = Duration::from_millis(expires_at.saturating_sub(Date::now().as_millis()));
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(e) = storage.set_alarm(delay).await {
+                    console_error!("failed to set alarm: {e}");
+                }
+            });
+        }
+    }
+
+    /// sends the player their seat token
+    fn send_seat(seat: &Seat, crosses: bool) -> Result<()> {
+        if let Some(session) = &seat.session {
+            send_message(
+                &ServerMessage::Seated(SeatAssignment {
+                    token: seat.token.clone(),
+                    crosses,
+                }),
+                session,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// request to join a game. The first player to join chooses the options.
+    fn join(&mut self, session: &Rc<WebSocket>, options: &GameOptions) -> Result<()> {
+        if [&self.crosses, &self.noughts]
+            .into_iter()
+            .flatten()
+            .any(|seat| seat.is(session))
+            || self.spectators.contains(session)
+        {
+            return Err(Error::RustError("you have already joined".to_string()));
+        }
+
         // First player to join is crosses
-        if self.crosses_session.is_none() {
-            self.crosses_session = Some(session.clone());
+        if self.crosses.is_none() {
+            self.options = *options;
+            let seat = Seat::new(session);
+            Self::send_seat(&seat, true)?;
+            self.crosses = Some(seat);
             send_message(&ServerMessage::WaitingForOpponentYouAreCrosses, session)?;
+            self.save();
             return Ok(());
         }
 
         // Second player joining starts the game
-        if self.noughts_session.is_none() {
-            self.noughts_session = Some(session.clone());
+        if self.noughts.is_none() {
+            let seat = Seat::new(session);
+            Self::send_seat(&seat, false)?;
+            self.noughts = Some(seat);
             self.new_game()?;
             return Ok(());
         }
 
-        Err(Error::RustError("this game is full".to_string()))
+        // Anyone else watches the game
+        send_message(&ServerMessage::Spectating, session)?;
+        send_message(&ServerMessage::GameUpdate(self.update(false)), session)?;
+        self.spectators.push(session.clone());
+
+        Ok(())
+    }
+
+    /// request to take back a seat after reconnecting. An unknown token joins as a new player.
+    fn reclaim(
+        &mut self,
+        session: &Rc<WebSocket>,
+        token: &SeatToken,
+        options: &GameOptions,
+    ) -> Result<()> {
+        let crosses = if self
+            .crosses
+            .as_ref()
+            .is_some_and(|seat| &seat.token == token)
+        {
+            true
+        } else if self
+            .noughts
+            .as_ref()
+            .is_some_and(|seat| &seat.token == token)
+        {
+            false
+        } else {
+            return self.join(session, options);
+        };
+
+        let seat = if crosses {
+            &mut self.crosses
+        } else {
+            &mut self.noughts
+        };
+        if let Some(seat) = seat {
+            // the previous connection may not have closed yet. The token takes precedence.
+            seat.session = Some(session.clone());
+            Self::send_seat(seat, crosses)?;
+        }
+
+        if self.noughts.is_none() {
+            send_message(&ServerMessage::WaitingForOpponentYouAreCrosses, session)?;
+            self.save();
+        } else {
+            self.notify()?;
+        }
+
+        Ok(())
+    }
+
+    /// request to play against the computer while waiting for an opponent
+    fn play_computer(&mut self, session: &Rc<WebSocket>, difficulty: Difficulty) -> Result<()> {
+        if !self.crosses.as_ref().is_some_and(|seat| seat.is(session)) || self.noughts.is_some() {
+            return Err(Error::RustError(
+                "only a player waiting for an opponent can play the computer".to_string(),
+            ));
+        }
+
+        self.noughts = Some(Seat::computer(difficulty));
+        self.new_game()
+    }
+
+    /// if it is the computer's turn, it makes its move
+    fn computer_move(&mut self) {
+        if self.crosses_turn || self.game.summary().is_finished() {
+            return;
+        }
+
+        let Some(difficulty) = self.noughts.as_ref().and_then(|seat| seat.computer) else {
+            return;
+        };
+
+        if matches!(self.game.board, GameBoard::Quantum(_)) {
+            // collapsing a cycle doesn't end the turn, so the computer may collapse then move
+            while let Some(m) = difficulty.choose_quantum_move(&self.game, Tile::Nought) {
+                if self.play(Play::Quantum(m), Tile::Nought).is_err() {
+                    return;
+                }
+                if let QuantumMove::Entangle(..) = m {
+                    self.crosses_turn = true;
+                    return;
+                }
+            }
+            return;
+        }
+
+        let play = match self.game.board {
+            GameBoard::Numerical(_) => difficulty
+                .choose_number_move(&self.game, Tile::Nought)
+                .map(|(pos, number)| Play::Number(pos, number)),
+            GameBoard::Gobblet(_) => difficulty
+                .choose_gobblet_move(&self.game, Tile::Nought)
+                .map(Play::Gobblet),
+            _ => difficulty
+                .choose_move(&self.game, Tile::Nought)
+                .map(|(position, symbol)| Play::Place { position, symbol }),
+        };
+        if let Some(play) = play {
+            if self.play(play, Tile::Nought).is_ok() {
+                self.crosses_turn = true;
+            }
+        }
+    }
+
+    /// plays the move and adds it to the record
+    fn play(&mut self, play: Play, player: Tile) -> std::result::Result<(), MoveError> {
+        self.game.apply(play, player)?;
+        self.record
+            .push(play, player, Date::now().as_millis(), &self.game);
+        Ok(())
+    }
+
+    /// the tile of the session's player, if it is their turn in a game in progress
+    fn mover(&self, session: &Rc<WebSocket>) -> Result<Tile> {
+        let cross_requesting = self.is_crosses(session)?;
+
+        if self.game.summary().is_finished() {
+            return Err(Error::RustError("the game is not in progress".to_string()));
+        }
+        if self.crosses_turn != cross_requesting {
+            return Err(Error::RustError("it is not your turn to move".to_string()));
+        }
+
+        Ok(if cross_requesting {
+            Tile::Cross
+        } else {
+            Tile::Nought
+        })
+    }
+
+    /// a player makes a move, then the computer replies if it is playing. Collapsing a quantum
+    /// cycle doesn't end the turn, since the player still has to move.
+    fn player_move(&mut self, session: &Rc<WebSocket>, play: Play) -> Result<()> {
+        let player = self.mover(session)?;
+        self.play(play, player)
+            .map_err(|e| Error::RustError(e.to_string()))?;
+
+        if !matches!(play, Play::Quantum(QuantumMove::Collapse(_))) {
+            // switch turns
+            self.crosses_turn = !self.crosses_turn;
+            self.computer_move();
+        }
+
+        self.notify()
     }
 
     /// clears the previous game and flips a coin to see who starts.
     pub fn new_game(&mut self) -> Result<()> {
-        self.board.clear();
+        self.game = Game::new(&self.options);
         self.crosses_requests_rematch = false;
         self.noughts_requests_rematch = false;
 
-        // flip a coin to see who starts
+        // flip a coin to see who starts. In order and chaos and numerical games it decides the
+        // roles instead, since order and the odd numbers always start.
         let mut r = rand::thread_rng();
         self.crosses_turn = r.gen();
+        let starter = if self.crosses_turn {
+            Tile::Cross
+        } else {
+            Tile::Nought
+        };
+        self.game.assign_roles(starter);
+        self.record = GameRecord::new(self.options, starter, Date::now().as_millis());
+        self.computer_move();
 
         // send opening board state to both players
         self.notify()?;
@@ -58,147 +423,229 @@ impl GameState {
         Ok(())
     }
 
-    /// called when a player leaves. The opposition is notified and the game ended.
-    /// if state were stored, this would be a good place to remove it.
+    /// called when a player disconnects. Their seat is held for them to reclaim, and the opposition is notified.
     pub fn player_left(&mut self, session: &Rc<WebSocket>) -> Result<()> {
-        // check the session is actually a player
-        let mut is_player = false;
-        if let Some(crosses_session) = &self.crosses_session {
-            if crosses_session.eq(session) {
-                is_player = true;
-            }
+        self.spectators.retain(|spectator| spectator != session);
+
+        let (seat, opponent) = if self.crosses.as_ref().is_some_and(|seat| seat.is(session)) {
+            (&mut self.crosses, &self.noughts)
+        } else if self.noughts.as_ref().is_some_and(|seat| seat.is(session)) {
+            (&mut self.noughts, &self.crosses)
+        } else {
+            // The connection could be someone quickly connecting and disconnecting from the game,
+            // or a connection replaced by a reclaimed seat.
+            return Ok(());
+        };
+
+        if let Some(seat) = seat {
+            seat.session = None;
+            seat.disconnected_at = Date::now().as_millis();
         }
-        if let Some(noughts_session) = &self.noughts_session {
-            if noughts_session.eq(session) {
-                is_player = true;
-            }
+
+        if let Some(opponent_session) = opponent.as_ref().and_then(|seat| seat.session.as_ref()) {
+            send_message(&ServerMessage::OpponentDisconnected, opponent_session)?;
         }
 
-        if !is_player {
-            // The connection could be someone quickly connecting and disconnecting from the game.
+        self.save();
+        self.schedule_expiry();
+
+        Ok(())
+    }
+
+    /// called when an alarm fires. If a disconnected player hasn't reclaimed their seat in time,
+    /// the opposition is notified, the game ended and removed from storage.
+    pub fn expire_seats(&mut self) -> Result<()> {
+        let now = Date::now().as_millis();
+        let expired = [&self.crosses, &self.noughts]
+            .into_iter()
+            .flatten()
+            .filter_map(Seat::expires_at)
+            .any(|expires_at| expires_at <= now);
+
+        if !expired {
+            self.schedule_expiry();
             return Ok(());
         }
 
-        // If both players are still connected, send the other player a close message and close the connection.
-        if let Some(crosses_session) = &self.crosses_session {
-            if let Some(noughts_session) = &self.noughts_session {
-                if crosses_session == session {
-                    send_message(&ServerMessage::GameEnded, noughts_session)?;
-                    // TODO: return from crosses session handler
-                }
-                if noughts_session == session {
-                    send_message(&ServerMessage::GameEnded, crosses_session)?;
-                    // TODO: return from noughts session handler
-                }
+        for session in [&self.crosses, &self.noughts]
+            .into_iter()
+            .flatten()
+            .filter_map(|seat| seat.session.as_ref())
+        {
+            send_message(&ServerMessage::GameEnded, session)?;
+        }
+        for session in &self.spectators {
+            if let Err(e) = send_message(&ServerMessage::GameEnded, session) {
+                console_error!("failed to notify spectator: {e}");
             }
         }
 
-        // clear the sessions to this game ID could be re-used.
-        self.crosses_session = None;
-        self.noughts_session = None;
+        // clear the seats so this game ID could be re-used.
+        self.crosses = None;
+        self.noughts = None;
+        self.delete();
 
         Ok(())
     }
 
-    /// notifies the sessions of the current state of play
-    /// if reconnects and persistence were to be supported, this should also save the state to storage
+    /// the current state of play
+    fn update(&self, turn: bool) -> GameUpdate {
+        GameUpdate {
+            game: self.game.clone(),
+            turn,
+            crosses_turn: self.crosses_turn,
+            winning_line: self.game.summary_with_line().1,
+            vanishing: self.game.vanishing(if self.crosses_turn {
+                Tile::Cross
+            } else {
+                Tile::Nought
+            }),
+        }
+    }
+
+    /// notifies the sessions of the current state of play and saves it to storage
     fn notify(&mut self) -> Result<()> {
-        if let Some(session) = &self.crosses_session {
+        if let Some(session) = self.crosses.as_ref().and_then(|seat| seat.session.as_ref()) {
             send_message(
-                &ServerMessage::GameUpdate(GameUpdate {
-                    board: self.board,
-                    turn: self.crosses_turn,
-                }),
+                &ServerMessage::GameUpdate(self.update(self.crosses_turn)),
                 session,
             )?;
         }
 
-        if let Some(session) = &self.noughts_session {
+        if let Some(session) = self.noughts.as_ref().and_then(|seat| seat.session.as_ref()) {
             send_message(
-                &ServerMessage::GameUpdate(GameUpdate {
-                    board: self.board,
-                    turn: !self.crosses_turn,
-                }),
+                &ServerMessage::GameUpdate(self.update(!self.crosses_turn)),
                 session,
             )?;
         }
 
+        // a spectator that can't be reached shouldn't stop the players' game
+        for session in &self.spectators {
+            if let Err(e) = send_message(&ServerMessage::GameUpdate(self.update(false)), session) {
+                console_error!("failed to update spectator: {e}");
+            }
+        }
+
+        self.save();
+
         Ok(())
     }
 
-    /// handles a client message
-    pub fn handle_message(&mut self, session: &Rc<WebSocket>, bytes: &[u8]) -> Result<()> {
-        let message: ClientMessage = bincode::deserialize(bytes)
-            .map_err(|_| Error::RustError("invalid message from client".to_string()))?;
+    /// whether the session is playing crosses. Errors if the session isn't playing or has no opponent yet.
+    fn is_crosses(&self, session: &Rc<WebSocket>) -> Result<bool> {
+        let crosses = if self.crosses.as_ref().is_some_and(|seat| seat.is(session)) {
+            true
+        } else if self.noughts.as_ref().is_some_and(|seat| seat.is(session)) {
+            false
+        } else if self.spectators.contains(session) {
+            return Err(Error::RustError("spectators can't play".to_string()));
+        } else {
+            return Err(Error::RustError(
+                "you are not playing in this game".to_string(),
+            ));
+        };
 
-        let crosses_session = self
-            .crosses_session
-            .clone()
-            .ok_or(Error::RustError("this game is full".to_string()))?;
-        let noughts_session = self
-            .noughts_session
-            .clone()
-            .ok_or(Error::RustError("this game is full".to_string()))?;
+        if self.noughts.is_none() {
+            return Err(Error::RustError("waiting for an opponent".to_string()));
+        }
+
+        Ok(crosses)
+    }
 
+    /// handles a client message. The options were requested when the session connected.
+    pub fn handle_message(
+        &mut self,
+        session: &Rc<WebSocket>,
+        message: ClientMessage,
+        options: &GameOptions,
+    ) -> Result<()> {
         match message {
-            ClientMessage::Move(pos) => {
-                let cross_requesting = crosses_session.eq(session);
-
-                match self.board.summary() {
-                    GameSummary::InProgress => {
-                        if self.crosses_turn != cross_requesting {
-                            return Err(Error::RustError(
-                                "it is not your turn to move".to_string(),
-                            ));
-                        }
+            ClientMessage::Join => self.join(session, options),
+            ClientMessage::Reclaim(token) => self.reclaim(session, &token, options),
+            ClientMessage::PlayComputer(difficulty) => self.play_computer(session, difficulty),
+            ClientMessage::Move(position) => {
+                let player = if self.is_crosses(session)? {
+                    Tile::Cross
+                } else {
+                    Tile::Nought
+                };
+                let symbol = self.game.default_symbol(player);
+                self.player_move(session, Play::Place { position, symbol })
+            }
+            ClientMessage::Place(position, symbol) => {
+                self.player_move(session, Play::Place { position, symbol })
+            }
+            ClientMessage::Drop(column) => {
+                let player = if self.is_crosses(session)? {
+                    Tile::Cross
+                } else {
+                    Tile::Nought
+                };
+                let position = self
+                    .game
+                    .landing(column)
+                    .map_err(|e| Error::RustError(e.to_string()))?;
+                let symbol = self.game.default_symbol(player);
+                self.player_move(session, Play::Place { position, symbol })
+            }
+            ClientMessage::Entangle(a, b) => {
+                self.player_move(session, Play::Quantum(QuantumMove::Entangle(a, b)))
+            }
+            ClientMessage::Collapse(cell) => {
+                self.player_move(session, Play::Quantum(QuantumMove::Collapse(cell)))
+            }
+            ClientMessage::Gobble(m) => self.player_move(session, Play::Gobblet(m)),
+            ClientMessage::Number(pos, number) => {
+                self.player_move(session, Play::Number(pos, number))
+            }
+            ClientMessage::RequestRematch => {
+                let cross_requesting = self.is_crosses(session)?;
+                let crosses_session = self.crosses.as_ref().and_then(|seat| seat.session.clone());
+                let noughts_session = self.noughts.as_ref().and_then(|seat| seat.session.clone());
 
-                        if self.board.tiles[pos.x()][pos.y()] == Tile::Unplayed {
-                            self.board.tiles[pos.x()][pos.y()] = if cross_requesting {
-                                Tile::Cross
-                            } else {
-                                Tile::Nought
-                            };
-                        } else {
-                            return Err(Error::RustError("invalid move".to_string()));
-                        }
-                    }
-                    _ => {
-                        return Err(Error::RustError("the game is not in progress".to_string()));
-                    }
+                // the computer is always up for a rematch
+                if self
+                    .noughts
+                    .as_ref()
+                    .is_some_and(|seat| seat.computer.is_some())
+                {
+                    self.noughts_requests_rematch = true;
                 }
 
-                // switch turns
-                self.crosses_turn = !self.crosses_turn;
-
-                self.notify()?;
-            }
-            ClientMessage::RequestRematch => {
                 // if this is the first time requesting rematch, save the client and ask the opponent
                 // for a rematch if they haven't asked already themselves
-                if crosses_session.eq(session) {
+                if cross_requesting {
                     if !self.crosses_requests_rematch {
                         self.crosses_requests_rematch = true;
                         if !self.noughts_requests_rematch {
-                            send_message(
-                                &ServerMessage::OppositionRequestsRematch,
-                                &noughts_session,
-                            )?;
+                            if let Some(noughts_session) = &noughts_session {
+                                send_message(
+                                    &ServerMessage::OppositionRequestsRematch,
+                                    noughts_session,
+                                )?;
+                            }
                         }
                     }
                 } else if !self.noughts_requests_rematch {
                     self.noughts_requests_rematch = true;
                     if !self.crosses_requests_rematch {
-                        send_message(&ServerMessage::OppositionRequestsRematch, &crosses_session)?;
+                        if let Some(crosses_session) = &crosses_session {
+                            send_message(
+                                &ServerMessage::OppositionRequestsRematch,
+                                crosses_session,
+                            )?;
+                        }
                     }
                 }
 
                 if self.crosses_requests_rematch && self.noughts_requests_rematch {
                     // both players have requested a rematch
-                    self.new_game()?;
+                    self.new_game()
+                } else {
+                    self.save();
+                    Ok(())
                 }
             }
         }
-
-        Ok(())
     }
 }